@@ -1,14 +1,15 @@
 #!/bin/bash
 
 # Send notification to a Zellij tab
-# Usage: notify-tab.sh <tab_number>
-#    or: notify-tab.sh <tab_name>
+# Usage: notify-tab.sh <tab_number> [message] [severity]
+#    or: notify-tab.sh <tab_name> [message] [severity]
 
 if [ $# -eq 0 ]; then
-    echo "Usage: $0 <tab_number> or $0 <tab_name>"
+    echo "Usage: $0 <tab_number> [message] [severity] or $0 <tab_name> [message] [severity]"
     echo "Examples:"
     echo "  $0 2        # Notify tab 2"
     echo "  $0 main     # Notify tab named 'main'"
+    echo "  $0 2 'tests passed' success"
     exit 1
 fi
 
@@ -17,8 +18,18 @@ ARG="$1"
 # Check if argument is a number
 if [[ "$ARG" =~ ^[0-9]+$ ]]; then
     # It's a tab number
-    zellij pipe --name "zj-status-sidebar:cli:notify" --args "tab=$ARG"
+    ARGS="tab=$ARG"
 else
     # It's a tab name
-    zellij pipe --name "zj-status-sidebar:cli:notify" --args "tab_name=$ARG"
-fi
\ No newline at end of file
+    ARGS="tab_name=$ARG"
+fi
+
+# Pipe args are comma separated, keep them out of the message
+if [ -n "$2" ]; then
+    ARGS="$ARGS,message=${2//,/;}"
+fi
+if [ -n "$3" ]; then
+    ARGS="$ARGS,severity=$3"
+fi
+
+zellij pipe --name "zj-status-sidebar:cli:notify" --args "$ARGS"
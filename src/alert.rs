@@ -0,0 +1,525 @@
+// Tab alerts and their lifecycle. Each alert type has a policy (how fast and
+// how long it blinks, how it looks afterwards, when it expires) taken from the
+// plugin config, and any pipe message raising an alert can override it.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{parse_in_range, parse_seconds};
+use crate::scheduler::EPSILON;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum AlertType {
+    CommandResult(CommandResult),
+    Notification,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CommandResult {
+    pub exit_code: u32,
+    pub command: Option<String>,
+    pub duration_ms: Option<u64>,
+}
+
+impl CommandResult {
+    pub fn exited(exit_code: u32) -> Self {
+        Self {
+            exit_code,
+            command: None,
+            duration_ms: None,
+        }
+    }
+
+    // From the `exit_code` (required), `command` and `duration_ms` pipe args
+    pub fn from_args(args: &BTreeMap<String, String>) -> Result<Self, String> {
+        let exit_code = match args.get("exit_code") {
+            Some(value) => value
+                .parse()
+                .map_err(|_| format!("exit_code: expected a number, got '{}'", value))?,
+            None => return Err(String::from("exit_code is required")),
+        };
+        let duration_ms = match args.get("duration_ms") {
+            Some(value) => Some(
+                value
+                    .parse()
+                    .map_err(|_| format!("duration_ms: expected milliseconds, got '{}'", value))?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            command: args.get("command").filter(|v| !v.is_empty()).cloned(),
+            duration_ms,
+            ..Self::exited(exit_code)
+        })
+    }
+
+    pub fn success(&self) -> bool {
+        self.exit_code == 0
+    }
+
+    // "✗ 101 · cargo test · 2m13s", leaving out what isn't known
+    pub fn details(&self) -> String {
+        let glyph = if self.success() { "✓" } else { "✗" };
+        let mut parts = vec![format!("{} {}", glyph, self.exit_code)];
+        parts.extend(self.command.clone());
+        parts.extend(self.duration_ms.map(format_duration));
+        parts.join(" · ")
+    }
+}
+
+// "850ms", "42s", "2m13s" or "1h05m"
+pub fn format_duration(ms: u64) -> String {
+    let secs = ms / 1000;
+    match secs {
+        0 => format!("{}ms", ms),
+        1..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn icon(self, icons: Icons) -> &'static str {
+        match (icons, self) {
+            (Icons::Emoji, Severity::Info) => "🔵",
+            (Icons::Emoji, Severity::Success) => "🟢",
+            (Icons::Emoji, Severity::Warning) => "🟡",
+            (Icons::Emoji, Severity::Error) => "🔴",
+            (Icons::Shapes, Severity::Info) => "ℹ",
+            (Icons::Shapes, Severity::Success) => "✓",
+            (Icons::Shapes, Severity::Warning) => "⚠",
+            (Icons::Shapes, Severity::Error) => "✗",
+        }
+    }
+}
+
+// Notification icons. The emoji dots only differ by color, shapes tell the
+// severities apart without it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Icons {
+    Emoji,
+    Shapes,
+}
+
+pub fn parse_icons(value: &str) -> Result<Icons, String> {
+    match value {
+        "emoji" => Ok(Icons::Emoji),
+        "shapes" => Ok(Icons::Shapes),
+        _ => Err(format!("expected emoji or shapes, got '{}'", value)),
+    }
+}
+
+// What a notification says. Everything is optional, a bare notify keeps the
+// original look of a red dot in the notification color.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Notice {
+    pub severity: Option<Severity>,
+    pub message: Option<String>,
+    pub source: Option<String>,
+}
+
+impl Notice {
+    // From the `severity`, `message` and `source` pipe args
+    pub fn from_args(args: &BTreeMap<String, String>) -> Result<Self, String> {
+        let non_empty = |key: &str| args.get(key).filter(|v| !v.is_empty()).cloned();
+        Ok(Self {
+            severity: args.get("severity").map(|v| parse_severity(v)).transpose()?,
+            message: non_empty("message"),
+            source: non_empty("source"),
+        })
+    }
+
+    pub fn icon(&self, icons: Icons) -> &'static str {
+        match self.severity {
+            Some(severity) => severity.icon(icons),
+            None if icons == Icons::Shapes => "●",
+            None => "🔴",
+        }
+    }
+
+    // "source: message", or whichever of the two is set
+    pub fn text(&self) -> Option<String> {
+        match (&self.source, &self.message) {
+            (Some(source), Some(message)) => Some(format!("{}: {}", source, message)),
+            (source, message) => source.clone().or_else(|| message.clone()),
+        }
+    }
+}
+
+fn parse_severity(value: &str) -> Result<Severity, String> {
+    match value {
+        "info" => Ok(Severity::Info),
+        "success" => Ok(Severity::Success),
+        "warning" => Ok(Severity::Warning),
+        "error" => Ok(Severity::Error),
+        _ => Err(format!("expected info, success, warning or error, got '{}'", value)),
+    }
+}
+
+// How an alert looks once it stopped blinking
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Steady {
+    Lit, // keep the highlight
+    Dim, // back to the unhighlighted look
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct AlertPolicy {
+    pub blink_interval: f64,
+    pub blink_count: Option<u32>, // None blinks until the alert is cleared
+    pub steady: Steady,
+    pub ttl: Option<f64>, // None keeps the alert until the tab is focused
+}
+
+impl AlertPolicy {
+    pub fn command_result() -> Self {
+        Self {
+            blink_interval: 1.0,
+            blink_count: None,
+            steady: Steady::Lit,
+            ttl: None,
+        }
+    }
+
+    pub fn notification() -> Self {
+        Self {
+            blink_interval: 0.2,
+            blink_count: Some(5),
+            steady: Steady::Lit,
+            ttl: None,
+        }
+    }
+
+    // Per-message overrides from pipe args: `blink_interval`, `blink_count`,
+    // `steady` and `ttl`, other args are left for the caller
+    pub fn apply_args(&mut self, args: &BTreeMap<String, String>) -> Result<(), String> {
+        if let Some(value) = args.get("blink_interval") {
+            self.blink_interval = parse_blink_interval(value)?;
+        }
+        if let Some(value) = args.get("blink_count") {
+            self.blink_count = parse_blink_count(value)?;
+        }
+        if let Some(value) = args.get("steady") {
+            self.steady = parse_steady(value)?;
+        }
+        if let Some(value) = args.get("ttl") {
+            self.ttl = parse_ttl(value)?;
+        }
+        Ok(())
+    }
+}
+
+// Faster blinking would keep every sidebar re-rendering nonstop
+const MIN_BLINK_INTERVAL: f64 = 0.05;
+
+// Seconds between blinks, at least MIN_BLINK_INTERVAL
+pub fn parse_blink_interval(value: &str) -> Result<f64, String> {
+    match parse_seconds(value)? {
+        v if v >= MIN_BLINK_INTERVAL => Ok(v),
+        _ => Err(format!("expected at least {} seconds, got '{}'", MIN_BLINK_INTERVAL, value)),
+    }
+}
+
+// A number of blinks, or "forever"
+pub fn parse_blink_count(value: &str) -> Result<Option<u32>, String> {
+    match value {
+        "forever" => Ok(None),
+        _ => parse_in_range(value, 0, 1000).map(Some),
+    }
+}
+
+pub fn parse_steady(value: &str) -> Result<Steady, String> {
+    match value {
+        "lit" => Ok(Steady::Lit),
+        "dim" => Ok(Steady::Dim),
+        _ => Err(format!("expected lit or dim, got '{}'", value)),
+    }
+}
+
+// Seconds until the alert clears itself, or "never"
+pub fn parse_ttl(value: &str) -> Result<Option<f64>, String> {
+    match value {
+        "never" => Ok(None),
+        _ => parse_seconds(value).map(Some),
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TabAlert {
+    pub alert_type: AlertType,
+    #[serde(default)]
+    pub notice: Notice,
+    // Set for alerts about a pane of the active tab, those stay until the
+    // pane itself is focused rather than the tab
+    #[serde(default)]
+    pub pane_id: Option<u32>,
+    pub policy: AlertPolicy,
+    pub lit: bool,
+    pub blinks_left: Option<u32>, // on/off toggles left, None blinks forever
+    pub age: f64,
+    pub since_toggle: f64,
+}
+
+impl TabAlert {
+    pub fn new(alert_type: AlertType, policy: AlertPolicy) -> Self {
+        Self {
+            // Command results start highlighted, notifications flash in
+            lit: matches!(alert_type, AlertType::CommandResult(_)),
+            alert_type,
+            notice: Notice::default(),
+            pane_id: None,
+            policy,
+            blinks_left: policy.blink_count.map(|n| n.saturating_mul(2)),
+            age: 0.0,
+            since_toggle: 0.0,
+        }
+    }
+
+    pub fn is_blinking(&self) -> bool {
+        self.blinks_left != Some(0)
+    }
+
+    // Whether the alert is drawn highlighted right now
+    pub fn is_lit(&self) -> bool {
+        if self.is_blinking() {
+            self.lit
+        } else {
+            self.policy.steady == Steady::Lit
+        }
+    }
+
+    // Advance the animation by `elapsed` seconds, returns false once the
+    // alert expired
+    pub fn tick(&mut self, elapsed: f64) -> bool {
+        self.age += elapsed;
+        if self.policy.ttl.is_some_and(|ttl| self.age + EPSILON >= ttl) {
+            return false;
+        }
+        if self.is_blinking() {
+            self.since_toggle += elapsed;
+            if self.since_toggle + EPSILON >= self.policy.blink_interval {
+                self.since_toggle = 0.0;
+                self.lit = !self.lit;
+                if let Some(left) = &mut self.blinks_left {
+                    *left -= 1;
+                }
+            }
+        }
+        true
+    }
+
+    // Seconds until the alert next needs a tick, None when it's static
+    pub fn next_tick(&self) -> Option<f64> {
+        let blink = self
+            .is_blinking()
+            .then_some(self.policy.blink_interval - self.since_toggle);
+        let expiry = self.policy.ttl.map(|ttl| ttl - self.age);
+        match (blink, expiry) {
+            (Some(a), Some(b)) => Some(a.min(b).max(0.0)),
+            (a, b) => a.or(b).map(|t| t.max(0.0)),
+        }
+    }
+}
+
+// Every alert a tab got since it was last focused, oldest first. The newest
+// one decides how the tab looks, the rest only show up in the badge count.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct AlertQueue {
+    alerts: Vec<TabAlert>,
+}
+
+impl AlertQueue {
+    // More than this and the badge stops being useful anyway
+    const CAPACITY: usize = 99;
+
+    pub fn push(&mut self, alert: TabAlert) {
+        if self.alerts.len() == Self::CAPACITY {
+            self.alerts.remove(0);
+        }
+        self.alerts.push(alert);
+    }
+
+    #[cfg(test)]
+    pub fn latest(&self) -> Option<&TabAlert> {
+        self.alerts.last()
+    }
+
+    // Latest alert to draw on the tab entry, alerts about one of
+    // `shown_panes` are drawn on that pane instead
+    pub fn latest_for_tab(&self, shown_panes: &[u32]) -> Option<&TabAlert> {
+        self.alerts
+            .iter()
+            .rev()
+            .find(|a| a.pane_id.is_none_or(|id| !shown_panes.contains(&id)))
+    }
+
+    pub fn latest_for_pane(&self, pane_id: u32) -> Option<&TabAlert> {
+        self.alerts.iter().rev().find(|a| a.pane_id == Some(pane_id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.alerts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.alerts.is_empty()
+    }
+
+    // The tab is in front, drop everything but alerts about panes that still
+    // aren't focused
+    pub fn clear_seen(&mut self, focused_pane: Option<u32>) {
+        self.alerts
+            .retain(|a| a.pane_id.is_some() && a.pane_id != focused_pane);
+    }
+
+    // "✓2 ✗1" for the command results in the queue, None without any
+    pub fn summary(&self) -> Option<String> {
+        let count = |success| {
+            self.alerts
+                .iter()
+                .filter(|a| matches!(&a.alert_type, AlertType::CommandResult(result) if result.success() == success))
+                .count()
+        };
+        let parts: Vec<String> = [("✓", count(true)), ("✗", count(false))]
+            .into_iter()
+            .filter(|(_, n)| *n > 0)
+            .map(|(glyph, n)| format!("{}{}", glyph, n))
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+
+    // Tick every alert, returns false once all of them expired
+    pub fn tick(&mut self, elapsed: f64) -> bool {
+        self.alerts.retain_mut(|alert| alert.tick(elapsed));
+        !self.alerts.is_empty()
+    }
+
+    pub fn next_tick(&self) -> Option<f64> {
+        self.alerts
+            .iter()
+            .filter_map(|alert| alert.next_tick())
+            .min_by(|a, b| a.total_cmp(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notification_flashes_then_stays_lit() {
+        let mut alert = TabAlert::new(AlertType::Notification, AlertPolicy::notification());
+        assert!(!alert.is_lit());
+        for _ in 0..10 {
+            assert!(alert.tick(0.2));
+        }
+        assert!(!alert.is_blinking());
+        assert!(alert.is_lit());
+        assert_eq!(alert.next_tick(), None);
+    }
+
+    #[test]
+    fn steady_dim_turns_the_highlight_off() {
+        let policy = AlertPolicy {
+            blink_count: Some(1),
+            steady: Steady::Dim,
+            ..AlertPolicy::notification()
+        };
+        let mut alert = TabAlert::new(AlertType::Notification, policy);
+        alert.tick(0.2);
+        assert!(alert.is_lit());
+        alert.tick(0.2);
+        assert!(!alert.is_lit());
+    }
+
+    #[test]
+    fn ttl_expires_the_alert() {
+        let policy = AlertPolicy {
+            ttl: Some(2.5),
+            ..AlertPolicy::command_result()
+        };
+        let mut alert = TabAlert::new(AlertType::CommandResult(CommandResult::exited(0)), policy);
+        assert!(alert.tick(1.0));
+        assert!(alert.tick(1.0));
+        assert_eq!(alert.next_tick(), Some(0.5));
+        assert!(!alert.tick(0.5));
+    }
+
+    #[test]
+    fn notice_text_combines_source_and_message() {
+        let args = BTreeMap::from([
+            ("severity".to_string(), "warning".to_string()),
+            ("message".to_string(), "disk almost full".to_string()),
+            ("source".to_string(), "df".to_string()),
+        ]);
+        let notice = Notice::from_args(&args).unwrap();
+        assert_eq!(notice.icon(Icons::Emoji), "🟡");
+        assert_eq!(notice.icon(Icons::Shapes), "⚠");
+        assert_eq!(notice.text().as_deref(), Some("df: disk almost full"));
+
+        let legacy = Notice::from_args(&BTreeMap::new()).unwrap();
+        assert_eq!(legacy.icon(Icons::Emoji), "🔴");
+        assert_eq!(legacy.text(), None);
+    }
+
+    #[test]
+    fn queue_counts_results_and_drops_expired_alerts() {
+        let mut queue = AlertQueue::default();
+        let short = AlertPolicy {
+            ttl: Some(1.0),
+            ..AlertPolicy::command_result()
+        };
+        queue.push(TabAlert::new(AlertType::CommandResult(CommandResult::exited(0)), AlertPolicy::command_result()));
+        queue.push(TabAlert::new(AlertType::CommandResult(CommandResult::exited(1)), short));
+        queue.push(TabAlert::new(AlertType::Notification, AlertPolicy::notification()));
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.summary().as_deref(), Some("✓1 ✗1"));
+        assert_eq!(queue.latest().unwrap().alert_type, AlertType::Notification);
+
+        assert!(queue.tick(1.0));
+        assert_eq!(queue.summary().as_deref(), Some("✓1"));
+    }
+
+    #[test]
+    fn command_result_details() {
+        let args = BTreeMap::from([
+            ("exit_code".to_string(), "101".to_string()),
+            ("command".to_string(), "cargo test".to_string()),
+            ("duration_ms".to_string(), "133000".to_string()),
+        ]);
+        let result = CommandResult::from_args(&args).unwrap();
+        assert_eq!(result.details(), "✗ 101 · cargo test · 2m13s");
+        assert_eq!(CommandResult::exited(0).details(), "✓ 0");
+        assert_eq!(format_duration(850), "850ms");
+        assert_eq!(format_duration(3_900_000), "1h05m");
+        assert!(CommandResult::from_args(&BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn pipe_args_override_the_policy() {
+        let mut policy = AlertPolicy::notification();
+        let args = BTreeMap::from([
+            ("blink_count".to_string(), "forever".to_string()),
+            ("ttl".to_string(), "30".to_string()),
+        ]);
+        policy.apply_args(&args).unwrap();
+        assert_eq!(policy.blink_count, None);
+        assert_eq!(policy.ttl, Some(30.0));
+
+        let bad = BTreeMap::from([("steady".to_string(), "bright".to_string())]);
+        assert!(policy.apply_args(&bad).is_err());
+        let too_fast = BTreeMap::from([("blink_interval".to_string(), "0.000001".to_string())]);
+        assert!(policy.apply_args(&too_fast).is_err());
+        assert_eq!(policy.blink_interval, 0.2);
+    }
+}
@@ -0,0 +1,328 @@
+// Typed plugin configuration parsed from the layout's `plugin { ... }` block
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use zellij_tile::prelude::PaletteColor;
+
+use crate::alert::{
+    parse_blink_count, parse_blink_interval, parse_icons, parse_steady, parse_ttl, AlertPolicy, Icons, Severity,
+};
+use crate::target::{program_name, Pattern};
+use crate::theme::{parse_preset, ColorOverrides, Preset};
+use crate::titles::TitleRule;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub collapsed: bool,
+    pub tab_height: usize,
+    pub mouse_wheel: MouseWheel,
+    pub show_panes: bool,
+    pub show_fullscreen: bool,
+    pub show_sync_panes: bool,
+    pub show_floating_panes: bool,
+    pub show_pane_count: bool,
+    pub show_keybinds: bool,
+    pub title: String,
+    pub generate_names: bool,
+    pub history_size: usize,
+    pub pane_alerts: bool,
+    pub auto_alert_tabs: Vec<Pattern>,     // tab name globs
+    pub auto_alert_commands: Vec<Pattern>, // command globs
+    pub title_rules: Vec<TitleRule>,
+    pub alert_icons: Icons,
+    pub command_alert: AlertPolicy,
+    pub notification_alert: AlertPolicy,
+    pub theme: Preset,
+    pub colors: ColorOverrides,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            collapsed: false,
+            tab_height: 3,
+            mouse_wheel: MouseWheel::Auto,
+            show_panes: false,
+            show_fullscreen: true,
+            show_sync_panes: true,
+            show_floating_panes: true,
+            show_pane_count: false,
+            show_keybinds: false,
+            title: String::from("{mode} {session}"),
+            generate_names: true,
+            history_size: 50,
+            pane_alerts: false,
+            auto_alert_tabs: Vec::new(),
+            auto_alert_commands: Vec::new(),
+            title_rules: Vec::new(),
+            alert_icons: Icons::Emoji,
+            command_alert: AlertPolicy::command_result(),
+            notification_alert: AlertPolicy::notification(),
+            theme: Preset::Zellij,
+            colors: ColorOverrides::default(),
+        }
+    }
+}
+
+// What the mouse wheel does over the sidebar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MouseWheel {
+    Auto,      // scroll the tab list when it overflows, switch tabs otherwise
+    SwitchTab, // always switch tabs
+    Scroll,    // always scroll the tab list
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    pub key: String,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+impl Config {
+    // Parse every known key, falling back to the default for values that fail
+    // validation. Errors are returned rather than dropped so they can be shown
+    // in the sidebar itself.
+    pub fn from_configuration(configuration: &BTreeMap<String, String>) -> (Self, Vec<ConfigError>) {
+        let mut config = Config::default();
+        let mut errors = Vec::new();
+
+        for (key, value) in configuration {
+            let result = match key.as_str() {
+                "collapsed" => parse_bool(value).map(|v| config.collapsed = v),
+                "tab_height" => parse_in_range(value, 1, 3).map(|v| config.tab_height = v),
+                "mouse_wheel" => parse_mouse_wheel(value).map(|v| config.mouse_wheel = v),
+                "show_panes" => parse_bool(value).map(|v| config.show_panes = v),
+                "show_fullscreen" => parse_bool(value).map(|v| config.show_fullscreen = v),
+                "show_sync_panes" => parse_bool(value).map(|v| config.show_sync_panes = v),
+                "show_floating_panes" => {
+                    parse_bool(value).map(|v| config.show_floating_panes = v)
+                }
+                "show_pane_count" => parse_bool(value).map(|v| config.show_pane_count = v),
+                "show_keybinds" => parse_bool(value).map(|v| config.show_keybinds = v),
+                "title" => {
+                    config.title = value.clone();
+                    Ok(())
+                }
+                "generate_names" => parse_bool(value).map(|v| config.generate_names = v),
+                "title_alert" => TitleRule::parse(None, value).map(|v| config.title_rules.push(v)),
+                "title_alert_info" => TitleRule::parse(Some(Severity::Info), value)
+                    .map(|v| config.title_rules.push(v)),
+                "title_alert_success" => TitleRule::parse(Some(Severity::Success), value)
+                    .map(|v| config.title_rules.push(v)),
+                "title_alert_warning" => TitleRule::parse(Some(Severity::Warning), value)
+                    .map(|v| config.title_rules.push(v)),
+                "title_alert_error" => TitleRule::parse(Some(Severity::Error), value)
+                    .map(|v| config.title_rules.push(v)),
+                "alert_icons" => parse_icons(value).map(|v| config.alert_icons = v),
+                "history_size" => parse_in_range(value, 0, 1000).map(|v| config.history_size = v),
+                "pane_alerts" => parse_bool(value).map(|v| config.pane_alerts = v),
+                "auto_alert_tabs" => parse_globs(value).map(|v| config.auto_alert_tabs = v),
+                "auto_alert_commands" => {
+                    parse_globs(value).map(|v| config.auto_alert_commands = v)
+                }
+                "command_alert_interval" => {
+                    parse_blink_interval(value).map(|v| config.command_alert.blink_interval = v)
+                }
+                "command_alert_blink_count" => {
+                    parse_blink_count(value).map(|v| config.command_alert.blink_count = v)
+                }
+                "command_alert_steady" => {
+                    parse_steady(value).map(|v| config.command_alert.steady = v)
+                }
+                "command_alert_ttl" => parse_ttl(value).map(|v| config.command_alert.ttl = v),
+                "notification_flash_interval" => {
+                    parse_blink_interval(value).map(|v| config.notification_alert.blink_interval = v)
+                }
+                "notification_flash_count" => {
+                    parse_blink_count(value).map(|v| config.notification_alert.blink_count = v)
+                }
+                "notification_steady" => {
+                    parse_steady(value).map(|v| config.notification_alert.steady = v)
+                }
+                "notification_ttl" => {
+                    parse_ttl(value).map(|v| config.notification_alert.ttl = v)
+                }
+                "theme" => parse_preset(value).map(|v| config.theme = v),
+                "text_color" => parse_color(value).map(|v| config.colors.text = Some(v)),
+                "background_color" => {
+                    parse_color(value).map(|v| config.colors.background = Some(v))
+                }
+                "active_tab_color" => {
+                    parse_color(value).map(|v| config.colors.active_tab = Some(v))
+                }
+                "inactive_tab_color" => {
+                    parse_color(value).map(|v| config.colors.inactive_tab = Some(v))
+                }
+                "header_color" => parse_color(value).map(|v| config.colors.header = Some(v)),
+                "separator_color" => {
+                    parse_color(value).map(|v| config.colors.separator = Some(v))
+                }
+                "success_color" => parse_color(value).map(|v| config.colors.success = Some(v)),
+                "failure_color" => parse_color(value).map(|v| config.colors.failure = Some(v)),
+                "notification_color" => {
+                    parse_color(value).map(|v| config.colors.notification = Some(v))
+                }
+                "info_color" => parse_color(value).map(|v| config.colors.info = Some(v)),
+                "warning_color" => parse_color(value).map(|v| config.colors.warning = Some(v)),
+                // Keys with a leading underscore are reserved for zellij itself
+                _ if key.starts_with('_') => Ok(()),
+                _ => Err(String::from("unknown key")),
+            };
+
+            if let Err(message) = result {
+                errors.push(ConfigError { key: key.clone(), message });
+            }
+        }
+
+        (config, errors)
+    }
+
+    pub fn auto_alerts(&self) -> bool {
+        !self.auto_alert_tabs.is_empty() || !self.auto_alert_commands.is_empty()
+    }
+
+    // Whether a command pane finishing gets an alert without `zw`, either
+    // its tab or its command has to match. Commands match on the whole
+    // command line or the program name alone.
+    pub fn auto_alerts_on(&self, tab_name: &str, command: &str) -> bool {
+        let program = program_name(command);
+        self.auto_alert_tabs.iter().any(|glob| glob.is_match(tab_name))
+            || self
+                .auto_alert_commands
+                .iter()
+                .any(|glob| glob.is_match(command) || glob.is_match(program))
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(format!("expected true or false, got '{}'", value)),
+    }
+}
+
+// Comma separated globs, e.g. "cargo *,make", compiled once here
+fn parse_globs(value: &str) -> Result<Vec<Pattern>, String> {
+    let globs: Vec<Pattern> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|glob| !glob.is_empty())
+        .map(Pattern::glob)
+        .collect();
+    if globs.is_empty() {
+        return Err(String::from("expected one or more comma separated patterns"));
+    }
+    Ok(globs)
+}
+
+fn parse_mouse_wheel(value: &str) -> Result<MouseWheel, String> {
+    match value {
+        "auto" => Ok(MouseWheel::Auto),
+        "switch_tab" => Ok(MouseWheel::SwitchTab),
+        "scroll" => Ok(MouseWheel::Scroll),
+        _ => Err(format!("expected auto, switch_tab or scroll, got '{}'", value)),
+    }
+}
+
+pub fn parse_in_range<T>(value: &str, min: T, max: T) -> Result<T, String>
+where
+    T: FromStr + PartialOrd + fmt::Display,
+{
+    match value.parse::<T>() {
+        Ok(v) if v >= min && v <= max => Ok(v),
+        _ => Err(format!("expected a number between {} and {}, got '{}'", min, max, value)),
+    }
+}
+
+pub fn parse_seconds(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(v) if v.is_finite() && v > 0.0 => Ok(v),
+        _ => Err(format!("expected a positive number of seconds, got '{}'", value)),
+    }
+}
+
+// Accepts "#rrggbb" hex colors or a 0-255 ANSI color index
+pub fn parse_color(value: &str) -> Result<PaletteColor, String> {
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or(0);
+            return Ok(PaletteColor::Rgb((channel(0), channel(2), channel(4))));
+        }
+    } else if let Ok(index) = value.parse::<u8>() {
+        return Ok(PaletteColor::EightBit(index));
+    }
+    Err(format!("expected '#rrggbb' or an ANSI color 0-255, got '{}'", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(pairs: &[(&str, &str)]) -> (Config, Vec<ConfigError>) {
+        let configuration = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Config::from_configuration(&configuration)
+    }
+
+    #[test]
+    fn valid_values_are_applied() {
+        let (config, errors) = parse(&[("collapsed", "true"), ("tab_height", "1"), ("mouse_wheel", "scroll")]);
+        assert!(errors.is_empty());
+        assert!(config.collapsed);
+        assert_eq!(config.tab_height, 1);
+        assert_eq!(config.mouse_wheel, MouseWheel::Scroll);
+    }
+
+    #[test]
+    fn invalid_values_keep_the_default_and_are_reported() {
+        let (config, errors) = parse(&[("tab_height", "4"), ("collapsed", "yes"), ("history_size", "-1")]);
+        assert_eq!(config, Config::default());
+        let keys: Vec<_> = errors.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["collapsed", "history_size", "tab_height"]);
+        assert_eq!(errors[2].to_string(), "tab_height: expected a number between 1 and 3, got '4'");
+    }
+
+    #[test]
+    fn blink_intervals_have_a_floor() {
+        let (config, errors) = parse(&[("command_alert_interval", "0.05"), ("notification_flash_interval", "0.001")]);
+        assert_eq!(config.command_alert.blink_interval, 0.05);
+        assert_eq!(config.notification_alert.blink_interval, AlertPolicy::notification().blink_interval);
+        assert_eq!(errors[0].to_string(), "notification_flash_interval: expected at least 0.05 seconds, got '0.001'");
+    }
+
+    #[test]
+    fn reserved_keys_are_ignored_and_unknown_keys_reported() {
+        let (_, errors) = parse(&[("_allow_exec_host_cmd", "true"), ("colapsed", "true")]);
+        assert_eq!(errors, [ConfigError { key: "colapsed".to_string(), message: "unknown key".to_string() }]);
+    }
+
+    #[test]
+    fn auto_alerts_match_the_tab_or_the_program() {
+        let (config, _) = parse(&[("auto_alert_tabs", "ci-*"), ("auto_alert_commands", "cargo *, make")]);
+        assert!(config.auto_alerts_on("ci-linux", "ls"));
+        assert!(config.auto_alerts_on("editor", "cargo test"));
+        assert!(config.auto_alerts_on("editor", "/usr/bin/make -j8"));
+        assert!(!config.auto_alerts_on("editor", "cargo"));
+        assert_eq!(config, parse(&[("auto_alert_tabs", "ci-*"), ("auto_alert_commands", "cargo *,make")]).0);
+    }
+
+    #[test]
+    fn colors_are_hex_or_ansi_indexes() {
+        assert_eq!(parse_color("#e06c75"), Ok(PaletteColor::Rgb((0xe0, 0x6c, 0x75))));
+        assert_eq!(parse_color("208"), Ok(PaletteColor::EightBit(208)));
+        assert!(parse_color("#e06c7").is_err());
+        assert!(parse_color("256").is_err());
+        assert!(parse_color("red").is_err());
+    }
+}
@@ -0,0 +1,145 @@
+// Keybinding hint footer built from the active mode's keybinds in ModeInfo
+
+use unicode_width::UnicodeWidthStr;
+use zellij_tile::prelude::actions::Action;
+use zellij_tile::prelude::*;
+
+// Hints worth showing in each mode, in display order
+fn hint_labels(mode: InputMode) -> &'static [&'static str] {
+    match mode {
+        InputMode::Normal => &["lock", "pane", "tab", "resize", "move", "scroll", "session"],
+        InputMode::Locked => &["unlock"],
+        InputMode::Pane => &["new", "close", "fullscreen", "float", "rename"],
+        InputMode::Tab => &["new", "close", "rename", "move", "sync"],
+        InputMode::Resize => &["grow", "shrink"],
+        InputMode::Move => &["move"],
+        InputMode::Scroll => &["down", "up", "search", "edit"],
+        InputMode::Session => &["detach", "manager"],
+        _ => &[],
+    }
+}
+
+// Label of the hint a keybind triggers, judged by its first action, the
+// rest is usually a trailing "SwitchToMode Normal"
+fn hint_label(mode: InputMode, action: &Action) -> Option<&'static str> {
+    let label = match (mode, action) {
+        (InputMode::Normal, Action::SwitchToMode(target)) => match target {
+            InputMode::Locked => "lock",
+            InputMode::Pane => "pane",
+            InputMode::Tab => "tab",
+            InputMode::Resize => "resize",
+            InputMode::Move => "move",
+            InputMode::Scroll => "scroll",
+            InputMode::Session => "session",
+            _ => return None,
+        },
+        (InputMode::Locked, Action::SwitchToMode(InputMode::Normal)) => "unlock",
+        (InputMode::Pane, Action::NewPane(..)) => "new",
+        (InputMode::Pane, Action::CloseFocus) => "close",
+        (InputMode::Pane, Action::ToggleFocusFullscreen) => "fullscreen",
+        (InputMode::Pane, Action::ToggleFloatingPanes) => "float",
+        (InputMode::Pane, Action::SwitchToMode(InputMode::RenamePane)) => "rename",
+        (InputMode::Tab, Action::NewTab(..)) => "new",
+        (InputMode::Tab, Action::CloseTab) => "close",
+        (InputMode::Tab, Action::SwitchToMode(InputMode::RenameTab)) => "rename",
+        (InputMode::Tab, Action::MoveTab(..)) => "move",
+        (InputMode::Tab, Action::ToggleActiveSyncTab) => "sync",
+        (InputMode::Resize, Action::Resize(Resize::Increase, _)) => "grow",
+        (InputMode::Resize, Action::Resize(Resize::Decrease, _)) => "shrink",
+        (InputMode::Move, Action::MovePane(..)) => "move",
+        (InputMode::Scroll, Action::ScrollDown) => "down",
+        (InputMode::Scroll, Action::ScrollUp) => "up",
+        (InputMode::Scroll, Action::SwitchToMode(InputMode::EnterSearch)) => "search",
+        (InputMode::Scroll, Action::EditScrollback) => "edit",
+        (InputMode::Session, Action::Detach) => "detach",
+        (InputMode::Session, Action::LaunchOrFocusPlugin(..)) => "manager",
+        _ => return None,
+    };
+    Some(label)
+}
+
+// (key, label) pairs for the current mode, using the first key bound to each
+// hint
+pub fn mode_hints(mode_info: &ModeInfo) -> Vec<(String, &'static str)> {
+    let keybinds = mode_info.get_mode_keybinds();
+    hint_labels(mode_info.mode)
+        .iter()
+        .filter_map(|label| {
+            keybinds
+                .iter()
+                .find(|(_, actions)| {
+                    actions
+                        .first()
+                        .and_then(|action| hint_label(mode_info.mode, action))
+                        == Some(*label)
+                })
+                .map(|(key, _)| (key.to_string(), *label))
+        })
+        .collect()
+}
+
+// Pack hints into lines no wider than `cols`, wrapping onto new lines rather
+// than truncating
+pub fn wrap_hints(hints: &[(String, &'static str)], cols: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for (key, label) in hints {
+        let hint = format!("<{}> {}", key, label);
+        if current.is_empty() {
+            current = format!(" {}", hint);
+        } else if current.width() + hint.width() + 2 <= cols {
+            current.push_str("  ");
+            current.push_str(&hint);
+        } else {
+            lines.push(std::mem::take(&mut current));
+            current = format!(" {}", hint);
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> KeyWithModifier {
+        KeyWithModifier::new(BareKey::Char(c))
+    }
+
+    #[test]
+    fn labels_follow_the_first_action_of_a_keybind() {
+        let lock = Action::SwitchToMode(InputMode::Locked);
+        assert_eq!(hint_label(InputMode::Normal, &lock), Some("lock"));
+        assert_eq!(hint_label(InputMode::Tab, &lock), None);
+        assert_eq!(hint_label(InputMode::Tab, &Action::CloseTab), Some("close"));
+    }
+
+    #[test]
+    fn mode_hints_pick_the_first_key_in_display_order() {
+        let mode_info = ModeInfo {
+            mode: InputMode::Tab,
+            keybinds: vec![(
+                InputMode::Tab,
+                vec![
+                    (key('x'), vec![Action::CloseTab, Action::SwitchToMode(InputMode::Normal)]),
+                    (key('n'), vec![Action::NewTab(None, vec![], None, None, None, true)]),
+                    (key('q'), vec![Action::CloseTab]),
+                ],
+            )],
+            ..Default::default()
+        };
+        let hints = mode_hints(&mode_info);
+        assert_eq!(hints, [(key('n').to_string(), "new"), (key('x').to_string(), "close")]);
+    }
+
+    #[test]
+    fn wrap_measures_display_width() {
+        let hints = [("a".to_string(), "b"), ("日本".to_string(), "x")];
+        // 14 chars but 16 columns wide, the CJK key takes two columns each
+        assert_eq!(wrap_hints(&hints, 16), [" <a> b  <日本> x"]);
+        assert_eq!(wrap_hints(&hints, 15), [" <a> b", " <日本> x"]);
+    }
+}
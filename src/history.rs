@@ -0,0 +1,206 @@
+// Recent alerts, kept after the alert itself is cleared so there's a record
+// of what finished while nobody was looking. Bounded, the oldest entries are
+// dropped first.
+
+use std::collections::VecDeque;
+
+use serde::Serialize;
+
+use crate::alert::{format_duration, AlertType, Icons, Severity};
+use crate::identity::TabId;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    Success,
+    Failure,
+    Notification,
+}
+
+impl EntryKind {
+    pub fn of(alert_type: &AlertType) -> Self {
+        match alert_type {
+            AlertType::CommandResult(result) if result.success() => EntryKind::Success,
+            AlertType::CommandResult(_) => EntryKind::Failure,
+            AlertType::Notification => EntryKind::Notification,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub at: u64, // unix seconds
+    #[serde(skip)]
+    pub tab_id: TabId,
+    pub tab: usize, // 1-based position when the alert was raised
+    pub tab_name: String,
+    pub kind: EntryKind,
+    pub severity: Option<Severity>,
+    pub message: Option<String>,
+    pub exit_code: Option<u32>,
+    pub command: Option<String>,
+    pub duration_ms: Option<u64>,
+    pub unread: bool,
+}
+
+impl HistoryEntry {
+    pub fn icon(&self, icons: Icons) -> &'static str {
+        match (self.kind, self.severity) {
+            (EntryKind::Success, _) => "✓",
+            (EntryKind::Failure, _) => "✗",
+            (EntryKind::Notification, Some(severity)) => severity.icon(icons),
+            (EntryKind::Notification, None) if icons == Icons::Shapes => "●",
+            (EntryKind::Notification, None) => "🔴",
+        }
+    }
+
+    // One line summary for the history view, e.g.
+    // "✗ 3m build · exit 101 · cargo test · 2m13s"
+    pub fn summary(&self, now: u64, icons: Icons) -> String {
+        let mut parts = vec![format!(
+            "{} {} {}",
+            self.icon(icons),
+            format_age(now.saturating_sub(self.at)),
+            self.tab_name
+        )];
+        if let Some(message) = &self.message {
+            parts.push(message.clone());
+        } else {
+            parts.extend(self.exit_code.map(|code| format!("exit {}", code)));
+            parts.extend(self.command.clone());
+            parts.extend(self.duration_ms.map(format_duration));
+        }
+        parts.join(" · ")
+    }
+}
+
+#[derive(Serialize)]
+struct HistoryJson<'a> {
+    unread: usize,
+    entries: Vec<&'a HistoryEntry>,
+}
+
+#[derive(Debug)]
+pub struct History {
+    entries: VecDeque<HistoryEntry>, // oldest first
+    capacity: usize,
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, entry: HistoryEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    // The tab was looked at, its alerts count as seen
+    pub fn mark_read(&mut self, tab_id: TabId) -> bool {
+        let mut changed = false;
+        for entry in self.entries.iter_mut().filter(|e| e.tab_id == tab_id && e.unread) {
+            entry.unread = false;
+            changed = true;
+        }
+        changed
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.entries.iter().filter(|e| e.unread).count()
+    }
+
+    // Newest first
+    pub fn entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn to_json(&self) -> String {
+        let json = HistoryJson {
+            unread: self.unread_count(),
+            entries: self.entries().collect(),
+        };
+        serde_json::to_string(&json).unwrap_or_default()
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(50)
+    }
+}
+
+// Compact age like "now", "42s", "3m", "5h" or "2d"
+pub fn format_age(secs: u64) -> String {
+    match secs {
+        0..=4 => String::from("now"),
+        5..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(tab_id: TabId, kind: EntryKind) -> HistoryEntry {
+        HistoryEntry {
+            at: 100,
+            tab_id,
+            tab: tab_id + 1,
+            tab_name: format!("t{}", tab_id),
+            kind,
+            severity: None,
+            message: None,
+            exit_code: None,
+            command: None,
+            duration_ms: None,
+            unread: true,
+        }
+    }
+
+    #[test]
+    fn drops_the_oldest_entries_past_capacity() {
+        let mut history = History::new(2);
+        for tab_id in 0..3 {
+            history.push(entry(tab_id, EntryKind::Success));
+        }
+        let tabs: Vec<_> = history.entries().map(|e| e.tab_id).collect();
+        assert_eq!(tabs, [2, 1]);
+    }
+
+    #[test]
+    fn focusing_a_tab_marks_its_entries_read() {
+        let mut history = History::default();
+        history.push(entry(0, EntryKind::Failure));
+        history.push(entry(1, EntryKind::Notification));
+        assert!(history.mark_read(0));
+        assert!(!history.mark_read(0));
+        assert_eq!(history.unread_count(), 1);
+    }
+
+    #[test]
+    fn serializes_newest_first_with_unread_count() {
+        let mut history = History::default();
+        history.push(HistoryEntry {
+            exit_code: Some(101),
+            command: Some("cargo test".to_string()),
+            ..entry(0, EntryKind::Failure)
+        });
+        assert_eq!(
+            history.to_json(),
+            r#"{"unread":1,"entries":[{"at":100,"tab":1,"tab_name":"t0","kind":"failure","severity":null,"message":null,"exit_code":101,"command":"cargo test","duration_ms":null,"unread":true}]}"#
+        );
+        assert_eq!(history.entries().next().unwrap().summary(280, Icons::Emoji), "✗ 3m t0 · exit 101 · cargo test");
+    }
+}
@@ -0,0 +1,52 @@
+// Side effects on zellij. Handlers queue these instead of calling the plugin
+// API directly, `ZellijPlugin` runs the queue after each event, and tests
+// inspect it instead.
+
+use zellij_tile::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostCommand {
+    RequestPermission(Vec<PermissionType>),
+    Subscribe(Vec<EventType>),
+    SetSelectable(bool),
+    SetTimeout(f64),
+    SwitchTabTo(u32),
+    SwitchToInputMode(InputMode),
+    FocusTerminalPane(u32),
+    FocusPluginPane(u32),
+    CliPipeOutput(String, String), // (pipe id, output)
+    // (destination plugin id, name, payload), None for every plugin
+    MessageToPlugin(Option<u32>, String, String),
+}
+
+impl HostCommand {
+    // Focus a pane on whichever tab it is, zellij switches tabs on its own
+    pub fn focus_pane(pane: &PaneInfo) -> Self {
+        if pane.is_plugin {
+            HostCommand::FocusPluginPane(pane.id)
+        } else {
+            HostCommand::FocusTerminalPane(pane.id)
+        }
+    }
+
+    pub fn run(self) {
+        match self {
+            HostCommand::RequestPermission(permissions) => request_permission(&permissions),
+            HostCommand::Subscribe(events) => subscribe(&events),
+            HostCommand::SetSelectable(selectable) => set_selectable(selectable),
+            HostCommand::SetTimeout(secs) => set_timeout(secs),
+            HostCommand::SwitchTabTo(tab) => switch_tab_to(tab),
+            HostCommand::SwitchToInputMode(mode) => switch_to_input_mode(&mode),
+            HostCommand::FocusTerminalPane(id) => focus_terminal_pane(id, false),
+            HostCommand::FocusPluginPane(id) => focus_plugin_pane(id, false),
+            HostCommand::CliPipeOutput(pipe_id, output) => cli_pipe_output(&pipe_id, &output),
+            HostCommand::MessageToPlugin(destination, name, payload) => {
+                let message = MessageToPlugin::new(name).with_payload(payload);
+                pipe_message_to_plugin(match destination {
+                    Some(id) => message.with_destination_plugin_id(id),
+                    None => message,
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// Stable tab identities. Zellij only reports tab positions, which shift when
+// tabs are closed or moved, so anything attached to a tab (generated names,
+// alerts, user meta) is keyed by a TabId that follows the tab instead.
+//
+// TabUpdate events are matched by unique tab name first, then by position.
+// PaneUpdate events then correct any guess using the panes each tab holds,
+// pane ids are never reused within a session.
+
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use zellij_tile::prelude::*;
+
+pub type TabId = usize;
+
+type PaneKey = (bool, u32); // (is_plugin, id), terminal and plugin ids overlap
+
+#[derive(Debug, Clone)]
+struct TrackedTab {
+    id: TabId,
+    name: String,
+    panes: HashSet<PaneKey>,
+}
+
+#[derive(Debug, Default)]
+pub struct TabTracker {
+    tabs: Vec<TrackedTab>, // indexed by tab position
+    next_id: TabId,
+}
+
+impl TabTracker {
+    pub fn id_at(&self, position: usize) -> Option<TabId> {
+        self.tabs.get(position).map(|t| t.id)
+    }
+
+    // Match the new tab list against the tracked one, returns the ids of
+    // tabs that are gone
+    pub fn update_tabs(&mut self, tabs: &[TabInfo]) -> Vec<TabId> {
+        let mut old: Vec<Option<TrackedTab>> = self.tabs.drain(..).map(Some).collect();
+        let mut matched: Vec<Option<TrackedTab>> = vec![None; tabs.len()];
+
+        // Names that appear exactly once on both sides identify a tab even
+        // after it moved
+        for (i, t) in tabs.iter().enumerate() {
+            let new_count = tabs.iter().filter(|n| n.name == t.name).count();
+            let old_count = old.iter().flatten().filter(|o| o.name == t.name).count();
+            if new_count == 1 && old_count == 1 {
+                let j = old.iter().position(|o| o.as_ref().is_some_and(|o| o.name == t.name));
+                matched[i] = j.and_then(|j| old[j].take());
+            }
+        }
+
+        // Otherwise assume the tab at the same position is the same tab
+        // (e.g. it was renamed)
+        for (i, slot) in matched.iter_mut().enumerate() {
+            if slot.is_none() {
+                if let Some(o) = old.get_mut(i) {
+                    *slot = o.take();
+                }
+            }
+        }
+
+        self.tabs = matched
+            .into_iter()
+            .zip(tabs)
+            .map(|(slot, t)| {
+                let mut tracked = slot.unwrap_or_else(|| {
+                    self.next_id += 1;
+                    TrackedTab {
+                        id: self.next_id - 1,
+                        name: String::new(),
+                        panes: HashSet::new(),
+                    }
+                });
+                tracked.name = t.name.clone();
+                tracked
+            })
+            .collect();
+
+        old.into_iter().flatten().map(|o| o.id).collect()
+    }
+
+    // Re-check identities against the panes each tab now holds, returns
+    // whether any tab changed identity
+    pub fn update_panes(&mut self, manifest: &PaneManifest) -> bool {
+        let pane_sets: Vec<HashSet<PaneKey>> = (0..self.tabs.len())
+            .map(|position| {
+                manifest
+                    .panes
+                    .get(&position)
+                    .map(|panes| panes.iter().map(|p| (p.is_plugin, p.id)).collect())
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        // Greedily pair positions with the tracked tab whose last known panes
+        // overlap the most
+        let mut candidates = Vec::new();
+        for (position, panes) in pane_sets.iter().enumerate() {
+            for (i, tracked) in self.tabs.iter().enumerate() {
+                let overlap = tracked.panes.intersection(panes).count();
+                if overlap > 0 {
+                    candidates.push((overlap, position, i));
+                }
+            }
+        }
+        candidates.sort_by_key(|c| Reverse(c.0));
+
+        let mut assigned: Vec<Option<usize>> = vec![None; self.tabs.len()];
+        let mut used = vec![false; self.tabs.len()];
+        for (_, position, i) in candidates {
+            if assigned[position].is_none() && !used[i] {
+                assigned[position] = Some(i);
+                used[i] = true;
+            }
+        }
+
+        // Positions without overlap keep their own identity when it's free,
+        // or take whichever one is left over
+        for position in 0..self.tabs.len() {
+            if assigned[position].is_none() && !used[position] {
+                assigned[position] = Some(position);
+                used[position] = true;
+            }
+        }
+        let mut leftover = (0..self.tabs.len()).filter(|i| !used[*i]);
+        let order: Vec<usize> = assigned
+            .into_iter()
+            .map(|a| a.or_else(|| leftover.next()).unwrap_or(0))
+            .collect();
+
+        let changed = order.iter().enumerate().any(|(position, i)| position != *i);
+        let mut tabs: Vec<TrackedTab> = order.iter().map(|i| self.tabs[*i].clone()).collect();
+        for (tracked, panes) in tabs.iter_mut().zip(pane_sets) {
+            tracked.panes = panes;
+        }
+        self.tabs = tabs;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::{one_pane_per_tab, pane, tab_infos};
+
+    fn ids(tracker: &TabTracker) -> Vec<TabId> {
+        (0..tracker.tabs.len()).filter_map(|position| tracker.id_at(position)).collect()
+    }
+
+    #[test]
+    fn closing_a_tab_keeps_the_others_ids() {
+        let mut tracker = TabTracker::default();
+        tracker.update_tabs(&tab_infos(&["a", "b", "c"], 0));
+        assert_eq!(tracker.update_tabs(&tab_infos(&["a", "c"], 0)), [1]);
+        assert_eq!(ids(&tracker), [0, 2]);
+        // New tabs never reuse an id
+        tracker.update_tabs(&tab_infos(&["a", "c", "d"], 0));
+        assert_eq!(ids(&tracker), [0, 2, 3]);
+    }
+
+    #[test]
+    fn moved_tabs_follow_their_name() {
+        let mut tracker = TabTracker::default();
+        tracker.update_tabs(&tab_infos(&["a", "b", "c"], 0));
+        assert!(tracker.update_tabs(&tab_infos(&["c", "a", "b"], 0)).is_empty());
+        assert_eq!(ids(&tracker), [2, 0, 1]);
+    }
+
+    #[test]
+    fn renamed_tab_keeps_its_position_id() {
+        let mut tracker = TabTracker::default();
+        tracker.update_tabs(&tab_infos(&["a", "b"], 0));
+        assert!(tracker.update_tabs(&tab_infos(&["a", "server"], 0)).is_empty());
+        assert_eq!(ids(&tracker), [0, 1]);
+    }
+
+    #[test]
+    fn panes_correct_a_move_between_same_named_tabs() {
+        let mut tracker = TabTracker::default();
+        tracker.update_tabs(&tab_infos(&["Tab", "Tab"], 0));
+        assert!(!tracker.update_panes(&one_pane_per_tab([1, 2].map(pane))));
+        // Names can't tell the two apart, the panes can
+        tracker.update_tabs(&tab_infos(&["Tab", "Tab"], 0));
+        assert!(tracker.update_panes(&one_pane_per_tab([2, 1].map(pane))));
+        assert_eq!(ids(&tracker), [1, 0]);
+    }
+
+    #[test]
+    fn pane_update_before_the_first_tab_update() {
+        let mut tracker = TabTracker::default();
+        assert!(!tracker.update_panes(&one_pane_per_tab([1, 2].map(pane))));
+        tracker.update_tabs(&tab_infos(&["Tab", "Tab"], 0));
+        assert_eq!(ids(&tracker), [0, 1]);
+        // Panes are only remembered for tabs that are known
+        assert!(!tracker.update_panes(&one_pane_per_tab([1, 2].map(pane))));
+        tracker.update_tabs(&tab_infos(&["Tab", "Tab"], 0));
+        assert!(tracker.update_panes(&one_pane_per_tab([2, 1].map(pane))));
+        assert_eq!(ids(&tracker), [1, 0]);
+    }
+}
@@ -0,0 +1,243 @@
+// Geometry of the tab list. Render and mouse hit-testing both go through
+// this so they can never disagree about which row belongs to which tab.
+//
+// Each entry is a tab label block of `entry_height` rows followed by one row
+// per child (the tab's panes when the pane tree is shown).
+
+use std::cmp::{max, min};
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabListLayout {
+    top: usize,    // 0-based row of the first tab list row
+    bottom: usize, // 0-based row after the last usable row
+    entry_height: usize,
+    child_counts: Vec<usize>,
+    scroll_offset: usize, // index of the first visible entry
+}
+
+// What sits under a given row
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayoutHit {
+    Entry(usize),
+    Child(usize, usize),
+    MoreAbove,
+    MoreBelow,
+}
+
+impl TabListLayout {
+    pub fn new(top: usize, bottom: usize, entry_height: usize, child_counts: Vec<usize>) -> Self {
+        Self {
+            top,
+            bottom: max(top, bottom),
+            entry_height: max(1, entry_height),
+            child_counts,
+            scroll_offset: 0,
+        }
+    }
+
+    pub fn with_scroll_offset(mut self, scroll_offset: usize) -> Self {
+        self.scroll_offset = min(scroll_offset, self.max_scroll_offset());
+        self
+    }
+
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    // Whether not every entry fits, in which case the first and last rows of
+    // the list are given to the "▲ N more" / "▼ N more" indicators when
+    // there is room for them
+    pub fn overflows(&self) -> bool {
+        let total: usize = (0..self.entry_count()).map(|i| self.total_height(i)).sum();
+        total > self.bottom - self.top
+    }
+
+    // Indicators only take their rows when a whole entry label block still
+    // fits between them, a short sidebar shows the entries alone
+    fn has_indicators(&self) -> bool {
+        self.overflows() && self.bottom - self.top >= self.entry_height + 2
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.child_counts.len()
+    }
+
+    // Row within an entry that holds the tab label: the only row for
+    // one-line entries, the first for two-line entries (the second is
+    // padding) and the middle one for three-line entries
+    pub fn label_offset(&self) -> usize {
+        (self.entry_height - 1) / 2
+    }
+
+    pub fn max_scroll_offset(&self) -> usize {
+        let count = self.entry_count();
+        if count == 0 {
+            return 0;
+        }
+        self.first_fitting_before(count - 1)
+    }
+
+    // Closest scroll offset to the current one that shows the given entry
+    pub fn scroll_offset_showing(&self, index: usize) -> usize {
+        if index < self.scroll_offset {
+            min(index, self.max_scroll_offset())
+        } else if index >= self.visible_entries().end {
+            min(self.first_fitting_before(index), self.max_scroll_offset())
+        } else {
+            self.scroll_offset
+        }
+    }
+
+    // Indices of the entries currently on screen. The first one is always
+    // included, clipped if it is taller than the list itself.
+    pub fn visible_entries(&self) -> Range<usize> {
+        let (start, end) = self.entry_area();
+        let count = self.entry_count();
+        if start == end || self.scroll_offset >= count {
+            return self.scroll_offset..self.scroll_offset;
+        }
+        let mut last = self.scroll_offset + 1;
+        let mut used = self.total_height(self.scroll_offset);
+        while last < count && used + self.total_height(last) <= end - start {
+            used += self.total_height(last);
+            last += 1;
+        }
+        self.scroll_offset..last
+    }
+
+    // 0-based rows of a visible entry's label block
+    pub fn entry_rows(&self, index: usize) -> Range<usize> {
+        let start = self.entry_start(index);
+        self.clip(start..start + self.entry_height)
+    }
+
+    // 0-based rows of a visible entry's children, one per child
+    pub fn child_rows(&self, index: usize) -> Range<usize> {
+        let start = self.entry_start(index) + self.entry_height;
+        self.clip(start..start + self.child_counts[index])
+    }
+
+    pub fn hit(&self, row: usize) -> Option<LayoutHit> {
+        if self.more_above().is_some_and(|(r, _)| r == row) {
+            return Some(LayoutHit::MoreAbove);
+        }
+        if self.more_below().is_some_and(|(r, _)| r == row) {
+            return Some(LayoutHit::MoreBelow);
+        }
+        self.visible_entries().find_map(|index| {
+            if self.entry_rows(index).contains(&row) {
+                Some(LayoutHit::Entry(index))
+            } else if self.child_rows(index).contains(&row) {
+                Some(LayoutHit::Child(index, row - self.child_rows(index).start))
+            } else {
+                None
+            }
+        })
+    }
+
+    // Row and hidden entry count of the "▲ N more" indicator
+    pub fn more_above(&self) -> Option<(usize, usize)> {
+        if self.has_indicators() && self.scroll_offset > 0 {
+            Some((self.top, self.scroll_offset))
+        } else {
+            None
+        }
+    }
+
+    // Row and hidden entry count of the "▼ N more" indicator
+    pub fn more_below(&self) -> Option<(usize, usize)> {
+        let hidden = self.entry_count().saturating_sub(self.visible_entries().end);
+        if self.has_indicators() && hidden > 0 {
+            Some((self.bottom - 1, hidden))
+        } else {
+            None
+        }
+    }
+
+    fn total_height(&self, index: usize) -> usize {
+        self.entry_height + self.child_counts[index]
+    }
+
+    fn entry_start(&self, index: usize) -> usize {
+        let above: usize = (self.scroll_offset..index).map(|i| self.total_height(i)).sum();
+        self.entry_area().0 + above
+    }
+
+    // Smallest offset at which every entry from it up to `index` fits
+    fn first_fitting_before(&self, index: usize) -> usize {
+        let (start, end) = self.entry_area();
+        let mut first = index;
+        let mut used = self.total_height(index);
+        while first > 0 && used + self.total_height(first - 1) <= end - start {
+            used += self.total_height(first - 1);
+            first -= 1;
+        }
+        first
+    }
+
+    fn clip(&self, rows: Range<usize>) -> Range<usize> {
+        let end = self.entry_area().1;
+        min(rows.start, end)..min(rows.end, end)
+    }
+
+    // Rows available to entries, excluding the indicator rows
+    fn entry_area(&self) -> (usize, usize) {
+        if self.has_indicators() {
+            (self.top + 1, self.bottom - 1)
+        } else {
+            (self.top, self.bottom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hit_maps_rows_to_entries_and_children() {
+        // Entry 0 is rows 2-4 with two children on rows 5-6, entry 1 is rows 7-9
+        let layout = TabListLayout::new(2, 20, 3, vec![2, 0]);
+        assert_eq!(layout.hit(1), None);
+        assert_eq!(layout.hit(2), Some(LayoutHit::Entry(0)));
+        assert_eq!(layout.hit(4), Some(LayoutHit::Entry(0)));
+        assert_eq!(layout.hit(6), Some(LayoutHit::Child(0, 1)));
+        assert_eq!(layout.hit(7), Some(LayoutHit::Entry(1)));
+        assert_eq!(layout.hit(10), None);
+    }
+
+    #[test]
+    fn overflow_reserves_indicator_rows() {
+        let layout = TabListLayout::new(0, 6, 1, vec![0; 10]).with_scroll_offset(2);
+        assert_eq!(layout.visible_entries(), 2..6);
+        assert_eq!(layout.hit(0), Some(LayoutHit::MoreAbove));
+        assert_eq!(layout.hit(1), Some(LayoutHit::Entry(2)));
+        assert_eq!(layout.more_below(), Some((5, 4)));
+    }
+
+    #[test]
+    fn short_list_keeps_the_active_entry_instead_of_indicators() {
+        // Two rows for ten tabs, the indicators would leave none for entries
+        let layout = TabListLayout::new(2, 4, 1, vec![0; 10]);
+        let layout = layout.clone().with_scroll_offset(layout.scroll_offset_showing(5));
+        assert!(layout.visible_entries().contains(&5));
+        assert_eq!(layout.more_above(), None);
+        assert_eq!(layout.more_below(), None);
+        assert_eq!(layout.hit(3), Some(LayoutHit::Entry(5)));
+
+        // Three row entries in four rows, the label row has to stay visible
+        let layout = TabListLayout::new(2, 6, 3, vec![0; 10]).with_scroll_offset(5);
+        assert_eq!(layout.visible_entries(), 5..6);
+        assert!(layout.entry_rows(5).contains(&(2 + layout.label_offset())));
+        assert_eq!(layout.more_below(), None);
+    }
+
+    #[test]
+    fn scroll_offset_showing_moves_the_least() {
+        let layout = TabListLayout::new(0, 6, 1, vec![0; 10]);
+        assert_eq!(layout.scroll_offset_showing(3), 0);
+        assert_eq!(layout.scroll_offset_showing(7), 4);
+        assert_eq!(layout.scroll_offset_showing(9), layout.max_scroll_offset());
+    }
+}
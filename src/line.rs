@@ -1,2 +1,38 @@
-// Line module - currently unused but kept for future extension
-// The code that was here used the old Palette API which is incompatible with zellij-tile 0.42.2
\ No newline at end of file
+// Header line: session name and current input mode, like compact-bar shows
+
+use zellij_tile::prelude::*;
+
+pub fn mode_name(mode: InputMode) -> String {
+    format!("{:?}", mode).to_uppercase()
+}
+
+// Fill in the `{session}` and `{mode}` placeholders of the title template
+pub fn header_text(template: &str, session_name: Option<&str>, mode: InputMode) -> String {
+    template
+        .replace("{session}", session_name.unwrap_or(""))
+        .replace("{mode}", &mode_name(mode))
+}
+
+// Collapsed header, only the expand marker and the mode's initial fit
+pub fn collapsed_header_text(mode: InputMode) -> String {
+    let initial = mode_name(mode).chars().next().unwrap_or(' ');
+    format!("»{}", initial)
+}
+
+// Background of the header for each mode, picked from the theme's emphasis
+// colors the same way the built-in bars tell modes apart
+pub fn mode_color(mode: InputMode, colors: &Styling) -> PaletteColor {
+    let emphasis = &colors.text_unselected;
+    match mode {
+        InputMode::Normal => emphasis.emphasis_2,
+        InputMode::Locked => colors.exit_code_error.base,
+        InputMode::Pane | InputMode::Tab => emphasis.emphasis_1,
+        InputMode::Resize | InputMode::Move => emphasis.emphasis_3,
+        InputMode::Scroll
+        | InputMode::EnterSearch
+        | InputMode::Search
+        | InputMode::RenameTab
+        | InputMode::RenamePane => emphasis.emphasis_0,
+        InputMode::Session | InputMode::Prompt | InputMode::Tmux => emphasis.emphasis_3,
+    }
+}
@@ -1,51 +1,68 @@
+mod alert;
+mod config;
+mod hints;
+mod history;
+mod host;
+mod identity;
+mod layout;
 mod line;
 mod tab;
+mod target;
+mod theme;
+mod titles;
 mod names;
+mod render;
+mod scheduler;
+#[cfg(test)]
+mod sim;
+mod sync;
+mod watch;
 
 use std::cmp::{max, min};
 use std::collections::{BTreeMap, HashMap};
 
-use unicode_width::{UnicodeWidthStr, UnicodeWidthChar};
 use zellij_tile::prelude::*;
-use zellij_tile_utils::style;
 
-use serde::{Deserialize, Serialize};
+use crate::alert::{AlertQueue, AlertType, CommandResult, Notice, TabAlert};
+use crate::config::{Config, ConfigError, MouseWheel};
+use crate::history::{EntryKind, History, HistoryEntry};
+use crate::host::HostCommand;
+use crate::identity::{TabId, TabTracker};
+use crate::layout::{LayoutHit, TabListLayout};
 use crate::names::NameCache;
-
-
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-enum AlertType {
-    CommandResult { success: bool },
-    Notification,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-struct TabAlert {
-    alert_type: AlertType,
-    alternate_color: bool,
-    flash_count: u8,  // For notifications, counts down from 5
-    persistent: bool, // For notifications, stays until tab is opened
-}
-
-impl Default for TabAlert {
-    fn default() -> Self {
-        Self {
-            alert_type: AlertType::CommandResult { success: true },
-            alternate_color: false,
-            flash_count: 0,
-            persistent: false,
-        }
-    }
-}
+use crate::scheduler::Scheduler;
+use crate::sync::{SyncClock, SyncState, SyncedTab};
+use crate::tab::TabMeta;
+use crate::target::Target;
+use crate::titles::TitleWatch;
+use crate::watch::CommandWatch;
 
 
 struct State {
     pane_info: PaneManifest,
-    tab_alerts: HashMap<usize, TabAlert>,
+    tab_alerts: HashMap<TabId, AlertQueue>,
+    alerts_ticked_at: f64,
     tabs: Vec<TabInfo>,
+    tab_ids: TabTracker,
     active_tab_idx: usize,
     mode_info: ModeInfo,
     name_cache: NameCache,
+    config: Config,
+    config_errors: Vec<ConfigError>,
+    tab_meta: HashMap<TabId, TabMeta>,
+    history: History,
+    show_history: bool,
+    plugin_id: Option<u32>,
+    sync: SyncClock,
+    pending_sync: Option<(u32, SyncState)>, // received before the tabs were known
+    caught_up: bool, // took the tabs of a catch-up answer
+    command_watch: CommandWatch,
+    title_watch: TitleWatch,
+    collapsed: bool,
+    scroll_offset: usize,
+    scroll_to_active: bool,
+    scheduler: Scheduler,
+    commands: Vec<HostCommand>,
     rows: usize,
     cols: usize,
 }
@@ -55,10 +72,28 @@ impl Default for State {
         Self {
             pane_info: PaneManifest::default(),
             tab_alerts: HashMap::new(),
+            alerts_ticked_at: 0.0,
             tabs: Vec::new(),
+            tab_ids: TabTracker::default(),
             active_tab_idx: 0,
             mode_info: ModeInfo::default(),
             name_cache: NameCache::new(),
+            config: Config::default(),
+            config_errors: Vec::new(),
+            tab_meta: HashMap::new(),
+            history: History::default(),
+            show_history: false,
+            plugin_id: None,
+            sync: SyncClock::default(),
+            pending_sync: None,
+            caught_up: false,
+            command_watch: CommandWatch::default(),
+            title_watch: TitleWatch::default(),
+            collapsed: false,
+            scroll_offset: 0,
+            scroll_to_active: true,
+            scheduler: Scheduler::default(),
+            commands: Vec::new(),
             rows: 0,
             cols: 0,
         }
@@ -68,18 +103,223 @@ impl Default for State {
 
 register_plugin!(State);
 
-impl State {}
+// The zellij host import only exists inside the wasm runtime, host-side test
+// builds link against this no-op instead
+#[cfg(all(test, not(target_family = "wasm")))]
+#[no_mangle]
+extern "C" fn host_run_plugin_command() {}
 
-impl ZellijPlugin for State {
-    fn load(&mut self, configuration: BTreeMap<String, String>) {
+impl State {
+    // 0-based row of the first tab entry, below the title, the config error
+    // row if any and the spacer row
+    fn first_tab_row(&self) -> usize {
+        2 + self.config_error_line().is_some() as usize
+    }
+
+    // Config errors take a single row so the tab list keeps its room, a lone
+    // error is shown as is and several are counted, all of them are logged
+    fn config_error_line(&self) -> Option<String> {
+        match &self.config_errors[..] {
+            [] => None,
+            [error] => Some(format!("⚠ {}", error)),
+            errors => Some(format!("⚠ {} config errors", errors.len())),
+        }
+    }
+
+    // Scrolled so the active tab stays in view after it changes, until the
+    // list is scrolled some other way
+    fn tab_list_layout(&self) -> TabListLayout {
+        let child_counts = self.tabs.iter().map(|t| self.pane_tree(t).len()).collect();
+        let bottom = self.rows.saturating_sub(self.footer_height());
+        let layout = TabListLayout::new(self.first_tab_row(), bottom, self.config.tab_height, child_counts)
+            .with_scroll_offset(self.scroll_offset);
+        match self.active_tab_idx.checked_sub(1) {
+            Some(active_tab_index) if self.scroll_to_active => {
+                let scroll_offset = layout.scroll_offset_showing(active_tab_index);
+                layout.with_scroll_offset(scroll_offset)
+            }
+            _ => layout,
+        }
+    }
+
+    // Keybinding hints for the current mode, capped to a third of the
+    // sidebar so the tab list keeps most of the room
+    fn footer_lines(&self) -> Vec<String> {
+        if !self.config.show_keybinds || self.collapsed {
+            return Vec::new();
+        }
+        let mut lines = hints::wrap_hints(&hints::mode_hints(&self.mode_info), self.cols);
+        lines.truncate(self.rows / 3);
+        lines
+    }
+
+    // Rows taken by the footer, including its separator row
+    fn footer_height(&self) -> usize {
+        match self.footer_lines().len() {
+            0 => 0,
+            n => n + 1,
+        }
+    }
+
+    // Panes listed under a tab entry, the collapsed sidebar is too narrow
+    // for the tree
+    fn pane_tree(&self, t: &TabInfo) -> Vec<&PaneInfo> {
+        if self.config.show_panes && !self.collapsed {
+            tab::listed_panes(self.pane_info.panes.get(&t.position))
+        } else {
+            Vec::new()
+        }
+    }
+
+    // Scroll the tab list itself, returns whether the offset changed
+    fn scroll_tab_list(&mut self, up: bool, amount: usize) -> bool {
+        let layout = self.tab_list_layout();
+        let current = layout.scroll_offset();
+        let scroll_offset = if up {
+            current.saturating_sub(amount)
+        } else {
+            layout.with_scroll_offset(current + amount).scroll_offset()
+        };
+        let changed = scroll_offset != current;
+        self.scroll_offset = scroll_offset;
+        self.scroll_to_active = false;
+        if changed {
+            self.publish_state();
+        }
+        changed
+    }
+
+    // Tabs a pipe message targets as (position, id) pairs, see target.rs for
+    // the accepted args. Invalid targets are logged and match nothing.
+    fn resolve_targets(&self, args: &BTreeMap<String, String>, what: &str) -> Vec<(usize, TabId)> {
+        match Target::from_args(args) {
+            Ok(target) => target
+                .resolve(&self.tabs, &self.pane_info)
+                .into_iter()
+                .filter_map(|idx| Some((idx, self.tab_ids.id_at(idx)?)))
+                .collect(),
+            Err(e) => {
+                eprintln!("[zj-status-sidebar] Invalid tab specified for {}: {}", what, e);
+                Vec::new()
+            }
+        }
+    }
+
+    // With pane_alerts on, the given pane of the active tab when it isn't the
+    // focused one
+    fn unfocused_pane(&self, idx: usize, pane_id: u32) -> Option<u32> {
+        if !self.config.pane_alerts || idx + 1 != self.active_tab_idx {
+            return None;
+        }
+        self.pane_info
+            .panes
+            .get(&idx)?
+            .iter()
+            .any(|p| !p.is_plugin && p.id == pane_id && !p.is_focused)
+            .then_some(pane_id)
+    }
+
+    // Drop the active tab's alerts, except pane alerts whose pane isn't
+    // focused yet. Returns whether anything was cleared.
+    fn clear_seen_alerts(&mut self, active_tab_index: usize) -> bool {
+        let Some(tab_id) = self.tab_ids.id_at(active_tab_index) else {
+            return false;
+        };
+        let Some(queue) = self.tab_alerts.get_mut(&tab_id) else {
+            return false;
+        };
+        let focused_pane = self
+            .pane_info
+            .panes
+            .get(&active_tab_index)
+            .and_then(|panes| panes.iter().find(|p| !p.is_plugin && p.is_focused))
+            .map(|p| p.id);
+        let before = queue.len();
+        queue.clear_seen(focused_pane);
+        let cleared = queue.len() != before;
+        if queue.is_empty() {
+            self.tab_alerts.remove(&tab_id);
+        }
+        cleared
+    }
+
+    // Name the tabs whose first terminal pane is known and that have no name
+    // yet, see names.rs. Returns whether any name was added.
+    fn generate_names(&mut self) -> bool {
+        if !self.config.generate_names {
+            return false;
+        }
+        let mut added = false;
+        for position in 0..self.tabs.len() {
+            let Some(tab_id) = self.tab_ids.id_at(position) else {
+                continue;
+            };
+            if let Some(pane_id) = tab::first_terminal_pane(self.pane_info.panes.get(&position)) {
+                added |= self.name_cache.generate(tab_id, pane_id);
+            }
+        }
+        added
+    }
+
+    fn showing_history(&self) -> bool {
+        self.show_history && !self.collapsed
+    }
+
+    // Every sidebar instance gets CLI pipes, only one answers queries so the
+    // caller sees a single reply: the lowest plugin id among the sidebars on
+    // the active tab, or among all of them when the active tab has none
+    fn answers_cli_queries(&self) -> bool {
+        let Some(plugin_id) = self.plugin_id else {
+            return true;
+        };
+        let all_panes = || self.pane_info.panes.values().flatten();
+        let plugin_url = all_panes()
+            .find(|p| p.is_plugin && p.id == plugin_id)
+            .map(|p| &p.plugin_url);
+        let is_sidebar = |p: &&PaneInfo| p.is_plugin && (p.id == plugin_id || Some(&p.plugin_url) == plugin_url);
+        let on_active_tab = self
+            .active_tab_idx
+            .checked_sub(1)
+            .and_then(|idx| self.pane_info.panes.get(&idx))
+            .into_iter()
+            .flatten()
+            .filter(is_sidebar)
+            .map(|p| p.id)
+            .min();
+        let answering = on_active_tab.or_else(|| all_panes().filter(is_sidebar).map(|p| p.id).min());
+        answering.is_none_or(|id| id == plugin_id)
+    }
+
+    fn wheel_scrolls_list(&self) -> bool {
+        match self.config.mouse_wheel {
+            MouseWheel::Auto => self.tab_list_layout().overflows(),
+            MouseWheel::SwitchTab => false,
+            MouseWheel::Scroll => true,
+        }
+    }
+}
+
+// Event handling. Host effects are queued as HostCommands instead of calling
+// zellij directly, so these can run (and be tested) outside of zellij.
+impl State {
+    fn handle_load(&mut self, configuration: BTreeMap<String, String>) {
         eprintln!("[zj-status-sidebar] Plugin instance loading at {:?}", std::time::SystemTime::now());
         eprintln!("[zj-status-sidebar] Config: {:?}", configuration);
-        request_permission(&[
+        let (config, config_errors) = Config::from_configuration(&configuration);
+        for error in &config_errors {
+            eprintln!("[zj-status-sidebar] Invalid config {}", error);
+        }
+        // Initial render mode, the collapsed layout only has room for the emoji
+        self.collapsed = config.collapsed;
+        self.history = History::new(config.history_size);
+        self.config = config;
+        self.config_errors = config_errors;
+        self.emit(HostCommand::RequestPermission(vec![
             PermissionType::ReadApplicationState,
             PermissionType::ChangeApplicationState,
             PermissionType::MessageAndLaunchOtherPlugins,
-        ]);
-        subscribe(&[
+        ]));
+        self.emit(HostCommand::Subscribe(vec![
             EventType::TabUpdate,
             EventType::PaneUpdate,
             EventType::ModeUpdate,
@@ -87,67 +327,58 @@ impl ZellijPlugin for State {
             EventType::Key,
             EventType::PermissionRequestResult,
             EventType::Timer,
-        ]);
+        ]));
         
         // Set session seed if we have session name
         if let Some(ref session_name) = self.mode_info.session_name {
             self.name_cache.set_session_seed(session_name);
         }
         
-        set_selectable(true);
+        self.emit(HostCommand::SetSelectable(true));
     }
 
-    fn update(&mut self, event: Event) -> bool {
+    fn handle_event(&mut self, event: Event) -> bool {
         let mut should_render = false;
         match event {
             Event::PaneUpdate(pane_info) => {
+                if self.config.show_panes && self.pane_info != pane_info {
+                    should_render = true;
+                }
+                if self.tab_ids.update_panes(&pane_info) {
+                    // A tab was matched to a different identity, its name,
+                    // alert and meta move with it
+                    should_render = true;
+                }
                 self.pane_info = pane_info;
+                // Tabs are reported before their panes, names can only be
+                // generated once a tab's first pane is known
+                should_render |= self.generate_names();
+                if let Some(active_tab_index) = self.active_tab_idx.checked_sub(1) {
+                    // A pane with an alert may have been focused
+                    should_render |= self.clear_seen_alerts(active_tab_index);
+                }
+                should_render |= self.alert_finished_commands();
+                should_render |= self.alert_title_changes();
             }
             Event::ModeUpdate(mode_info) => {
                 if self.mode_info != mode_info {
                     should_render = true;
                     
-                    // Set session seed when we get session name
+                    // Set session seed when we get session name, a new
+                    // seed drops the names generated with the old one
                     if let Some(ref session_name) = mode_info.session_name {
                         self.name_cache.set_session_seed(session_name);
+                        self.generate_names();
                     }
                 }
                 self.mode_info = mode_info
             }
             Event::Timer(_) => {
-                // Handle tab alerts and notifications
+                self.scheduler.fired();
                 if !self.tab_alerts.is_empty() {
-                    let mut alerts_to_remove = Vec::new();
-                    
-                    for (tab_idx, tab_alert) in self.tab_alerts.iter_mut() {
-                        match &tab_alert.alert_type {
-                            AlertType::CommandResult { .. } => {
-                                // Toggle color for command results
-                                tab_alert.alternate_color = !tab_alert.alternate_color;
-                            }
-                            AlertType::Notification => {
-                                // Handle notification flashing
-                                if tab_alert.flash_count > 0 {
-                                    tab_alert.alternate_color = !tab_alert.alternate_color;
-                                    if tab_alert.alternate_color {
-                                        // Only decrement on the "off" phase of flash
-                                        tab_alert.flash_count -= 1;
-                                    }
-                                } else if !tab_alert.persistent {
-                                    // Remove non-persistent notifications after flashing
-                                    alerts_to_remove.push(*tab_idx);
-                                }
-                            }
-                        }
-                    }
-                    
-                    // Remove finished alerts
-                    for idx in alerts_to_remove {
-                        self.tab_alerts.remove(&idx);
-                    }
-                    
+                    self.advance_alerts();
+                    self.schedule_alerts();
                     should_render = true;
-                    set_timeout(1.0); // Continue timer for alerts
                 }
             }
             Event::TabUpdate(tabs) => {
@@ -155,16 +386,29 @@ impl ZellijPlugin for State {
                     let active_tab_idx = active_tab_index + 1;
                     let tab_changed = self.active_tab_idx != active_tab_idx;
                     
+                    if tab_changed {
+                        // Bring the newly active tab into view, see tab_list_layout
+                        self.scroll_to_active = true;
+                    }
                     if tab_changed || self.tabs != tabs {
-                        // Remove alerts when tab becomes active (using position, not index)
-                        if active_tab_index < tabs.len() {
-                            let active_tab_position = tabs[active_tab_index].position;
-                            self.tab_alerts.remove(&active_tab_position);
+                        // Drop everything attached to closed tabs
+                        for tab_id in self.tab_ids.update_tabs(&tabs) {
+                            self.tab_alerts.remove(&tab_id);
+                            self.tab_meta.remove(&tab_id);
+                            self.name_cache.forget(tab_id);
+                        }
+                        // Remove alerts when tab becomes active
+                        self.clear_seen_alerts(active_tab_index);
+                        if let Some(active_tab_id) = self.tab_ids.id_at(active_tab_index) {
+                            self.history.mark_read(active_tab_id);
                         }
                         should_render = true;
                     }
                     self.active_tab_idx = active_tab_idx;
                     self.tabs = tabs;
+                    if let Some((writer, state)) = self.pending_sync.take() {
+                        should_render |= self.adopt_state(writer, state);
+                    }
                 } else {
                     eprintln!("Could not find active tab.");
                 }
@@ -175,7 +419,7 @@ impl ZellijPlugin for State {
                         KeyWithModifier { bare_key: BareKey::Char('t'), .. } => {
                             // Don't render here - let Zellij handle the mode switch
                             // The ModeUpdate event will trigger a render if needed
-                            switch_to_input_mode(&InputMode::Normal);
+                            self.emit(HostCommand::SwitchToInputMode(InputMode::Normal));
                         }
                         KeyWithModifier { bare_key: BareKey::Char('r'), .. } => {
                             // User pressed 'r' to rename tab - trigger a render to show rename UI
@@ -190,25 +434,67 @@ impl ZellijPlugin for State {
             }
             Event::Mouse(me) => match me {
                 Mouse::LeftClick(row, _col) => {
-                    if row >= 2 {
-                        let tab_height = 3;
-                        let tab_idx = (row as usize - 2) / tab_height;
-                        if tab_idx < self.tabs.len() {
-                            let tab_number = tab_idx + 1;
-                            switch_tab_to(tab_number as u32);
+                    if row == 0 {
+                        // Clicking the title row toggles collapsed/expanded
+                        self.collapsed = !self.collapsed;
+                        self.publish_state();
+                        should_render = true;
+                    } else if self.showing_history() {
+                        // Clicking anywhere in the history goes back to the tabs
+                        self.show_history = false;
+                        should_render = true;
+                    } else if row >= 0 {
+                        let row = row as usize;
+                        let layout = self.tab_list_layout();
+                        let page = max(layout.visible_entries().len(), 1);
+                        match layout.hit(row) {
+                            Some(LayoutHit::Entry(tab_idx)) => {
+                                let tab_number = tab_idx + 1;
+                                self.emit(HostCommand::SwitchTabTo(tab_number as u32));
+                            }
+                            Some(LayoutHit::Child(tab_idx, pane_idx)) => {
+                                if let Some(pane) = self.pane_tree(&self.tabs[tab_idx]).get(pane_idx) {
+                                    let command = HostCommand::focus_pane(pane);
+                                    self.emit(command);
+                                }
+                            }
+                            // Clicking an overflow indicator pages the list
+                            Some(LayoutHit::MoreAbove) => {
+                                should_render = self.scroll_tab_list(true, page);
+                            }
+                            Some(LayoutHit::MoreBelow) => {
+                                should_render = self.scroll_tab_list(false, page);
+                            }
+                            None => {}
                         }
                     }
                 }
-                Mouse::ScrollUp(_) => {
-                    switch_tab_to(min(self.active_tab_idx + 1, self.tabs.len()) as u32);
+                Mouse::ScrollUp(lines) => {
+                    if self.wheel_scrolls_list() {
+                        should_render = self.scroll_tab_list(true, max(lines, 1));
+                    } else {
+                        self.emit(HostCommand::SwitchTabTo(min(self.active_tab_idx + 1, self.tabs.len()) as u32));
+                    }
                 }
-                Mouse::ScrollDown(_) => {
-                    switch_tab_to(max(self.active_tab_idx.saturating_sub(1), 1) as u32);
+                Mouse::ScrollDown(lines) => {
+                    if self.wheel_scrolls_list() {
+                        should_render = self.scroll_tab_list(false, max(lines, 1));
+                    } else {
+                        self.emit(HostCommand::SwitchTabTo(max(self.active_tab_idx.saturating_sub(1), 1) as u32));
+                    }
                 }
                 _ => {}
             },
             Event::PermissionRequestResult(result) => match result {
-                PermissionStatus::Granted => set_selectable(false),
+                PermissionStatus::Granted => {
+                    self.emit(HostCommand::SetSelectable(false));
+                    // Catch up with the other sidebars
+                    self.emit(HostCommand::MessageToPlugin(
+                        None,
+                        sync::REQUEST_MESSAGE.to_string(),
+                        String::new(),
+                    ));
+                }
                 PermissionStatus::Denied => eprintln!("Permission denied by user."),
             },
             _ => {}
@@ -216,348 +502,425 @@ impl ZellijPlugin for State {
         should_render
     }
 
-    fn pipe(&mut self, pipe_message: PipeMessage) -> bool {
+    fn handle_pipe(&mut self, pipe_message: PipeMessage) -> bool {
         let mut should_render = false;
         match pipe_message.source {
             PipeSource::Keybind => {
                 if pipe_message.name == "toggle_collapse" {
-                    eprintln!("[zj-status-sidebar] Toggle keybind pressed (Ctrl+t,t) - feature temporarily disabled");
+                    // Every sidebar gets the keybind and toggles by itself
+                    self.collapsed = !self.collapsed;
+                    should_render = true;
+                } else if pipe_message.name == "toggle_history" && !self.collapsed {
+                    // The collapsed sidebar has no room for the history, a
+                    // toggle there would only surface on the next expand
+                    self.show_history = !self.show_history;
+                    should_render = true;
                 }
             }
-            PipeSource::Cli(_) => {
+            PipeSource::Cli(ref pipe_id) => {
                 if pipe_message.name == "zj-status-sidebar:cli:tab_alert" {
-                    if let (Some(pane_id_str), Some(exit_code_str)) = (
-                        pipe_message.args.get("pane_id"),
-                        pipe_message.args.get("exit_code"),
-                    ) {
-                        let pane_id: u32 = match pane_id_str.parse() {
-                            Ok(int) => int,
-                            Err(..) => return false,
-                        };
-                        let exit_code: u32 = match exit_code_str.parse() {
-                            Ok(int) => int,
-                            Err(..) => return false,
-                        };
-
-                        for (tab_idx, pane_vec) in &self.pane_info.panes {
-                            if *tab_idx == self.active_tab_idx - 1 {
-                                continue;
-                            }
-
-                            if pane_vec.iter().any(|p| p.id == pane_id) {
-                                let first_alert = self.tab_alerts.is_empty();
-
-                                self.tab_alerts.insert(
-                                    *tab_idx,
-                                    TabAlert {
-                                        alert_type: AlertType::CommandResult { success: exit_code == 0 },
-                                        alternate_color: true,
-                                        flash_count: 0,
-                                        persistent: false,
-                                    },
-                                );
-
-                                if first_alert {
-                                    set_timeout(1.0);
-                                    should_render = true;
-                                }
-
-                                break;
-                            }
+                    // Usage: zellij pipe --name "zj-status-sidebar:cli:tab_alert" --args "pane_id=$ZELLIJ_PANE_ID,exit_code=$?"
+                    //    optionally with "command=cargo test,duration_ms=133000"
+                    let result = match CommandResult::from_args(&pipe_message.args) {
+                        Ok(result) => result,
+                        Err(e) => {
+                            eprintln!("[zj-status-sidebar] Invalid command alert: {}", e);
+                            return false;
                         }
+                    };
+                    let mut policy = self.config.command_alert;
+                    if let Err(e) = policy.apply_args(&pipe_message.args) {
+                        eprintln!("[zj-status-sidebar] Invalid alert policy: {}", e);
+                        return false;
+                    }
+
+                    let pane_id = pipe_message.args.get("pane_id").and_then(|v| v.parse().ok());
+                    for (idx, tab_id) in self.resolve_targets(&pipe_message.args, "command alert") {
+                        let alert = TabAlert::new(AlertType::CommandResult(result.clone()), policy);
+                        should_render |= self.alert_tab(idx, tab_id, pane_id, alert);
                     }
                 } else if pipe_message.name == "zj-status-sidebar:cli:notify" {
                     // Handle notification request
                     // Usage: zellij pipe --name "zj-status-sidebar:cli:notify" --args "tab=1"
                     //    or: zellij pipe --name "zj-status-sidebar:cli:notify" --args "tab_name=main"
-                    
-                    let tab_idx = if let Some(tab_str) = pipe_message.args.get("tab") {
-                        // Tab specified by index (1-based)
-                        match tab_str.parse::<usize>() {
-                            Ok(idx) if idx > 0 => Some(idx - 1),
-                            _ => None,
+                    //    or any other target, see target.rs
+                    // optionally with "severity=warning,message=disk almost full,source=df"
+                    let mut policy = self.config.notification_alert;
+                    if let Err(e) = policy.apply_args(&pipe_message.args) {
+                        eprintln!("[zj-status-sidebar] Invalid alert policy: {}", e);
+                        return false;
+                    }
+                    let notice = match Notice::from_args(&pipe_message.args) {
+                        Ok(notice) => notice,
+                        Err(e) => {
+                            eprintln!("[zj-status-sidebar] Invalid notification: {}", e);
+                            return false;
                         }
-                    } else if let Some(tab_name) = pipe_message.args.get("tab_name") {
-                        // Tab specified by name
-                        self.tabs.iter().position(|t| t.name == *tab_name)
-                    } else {
-                        None
                     };
-                    
-                    if let Some(idx) = tab_idx {
-                        // Get the tab position for the tab alerts map
-                        if idx < self.tabs.len() {
-                            let tab_position = self.tabs[idx].position;
-                            
-                            // Don't notify the active tab
-                            if idx != self.active_tab_idx - 1 {
-                                let first_alert = self.tab_alerts.is_empty();
-                                
-                                self.tab_alerts.insert(
-                                    tab_position,
-                                    TabAlert {
-                                        alert_type: AlertType::Notification,
-                                        alternate_color: false,
-                                        flash_count: 10,  // 5 full flashes (on/off = 2 states)
-                                        persistent: true,
-                                    },
-                                );
-                                
-                                if first_alert {
-                                    set_timeout(0.2);  // Faster timer for flashing
+
+                    let pane_id = pipe_message.args.get("pane_id").and_then(|v| v.parse().ok());
+                    for (idx, tab_id) in self.resolve_targets(&pipe_message.args, "notification") {
+                        let alert = TabAlert {
+                            notice: notice.clone(),
+                            ..TabAlert::new(AlertType::Notification, policy)
+                        };
+                        if self.alert_tab(idx, tab_id, pane_id, alert) {
+                            should_render = true;
+                            eprintln!("[zj-status-sidebar] Notification sent to tab {} (id {})", idx + 1, tab_id);
+                        }
+                    }
+                } else if pipe_message.name == "zj-status-sidebar:cli:set_tab_meta" {
+                    // Pin an emoji, label or color to a tab without renaming it
+                    // Usage: zellij pipe --name "zj-status-sidebar:cli:set_tab_meta" --args "tab=2,emoji=🐛,label=bugfix"
+                    //    an empty value clears that override: --args "tab=2,label="
+                    for (_, tab_id) in self.resolve_targets(&pipe_message.args, "tab meta") {
+                        let mut meta = self.tab_meta.get(&tab_id).cloned().unwrap_or_default();
+                        match meta.apply_args(&pipe_message.args) {
+                            Ok(()) => {
+                                if meta.is_empty() {
+                                    self.tab_meta.remove(&tab_id);
+                                } else {
+                                    self.tab_meta.insert(tab_id, meta);
                                 }
                                 should_render = true;
-                                
-                                eprintln!("[zj-status-sidebar] Notification sent to tab {} (position {})", idx + 1, tab_position);
                             }
-                        } else {
-                            eprintln!("[zj-status-sidebar] Tab index out of range");
+                            Err(e) => {
+                                eprintln!("[zj-status-sidebar] Invalid tab meta: {}", e);
+                                break;
+                            }
                         }
-                    } else {
-                        eprintln!("[zj-status-sidebar] Invalid tab specified for notification");
+                    }
+                } else if pipe_message.name == "zj-status-sidebar:cli:history" {
+                    // Recent alerts as JSON, newest first
+                    // Usage: zellij pipe --name "zj-status-sidebar:cli:history"
+                    if self.answers_cli_queries() {
+                        let json = self.history.to_json();
+                        self.emit(HostCommand::CliPipeOutput(pipe_id.clone(), json));
                     }
                 }
             }
-            PipeSource::Plugin(_source_plugin_id) => {
-                if pipe_message.is_private
-                    && pipe_message.name == "zj-status-sidebar:plugin:tab_alert:broadcast"
-                {
-                    if self.tab_alerts.is_empty() {
-                        if let Some(payload) = &pipe_message.payload {
-                            if let Ok(new_alerts) = serde_json::from_str::<HashMap<usize, TabAlert>>(payload) {
-                                if self.tab_alerts != new_alerts {
-                                    self.tab_alerts = new_alerts;
-                                    set_timeout(1.0);
-                                    should_render = true;
+            PipeSource::Plugin(source_plugin_id) => {
+                // Broadcasts come back to the sender too
+                if source_plugin_id == self.own_plugin_id() {
+                    return false;
+                }
+                if pipe_message.name == sync::STATE_MESSAGE {
+                    let state = pipe_message
+                        .payload
+                        .as_deref()
+                        .map(serde_json::from_str::<SyncState>);
+                    match state {
+                        Some(Ok(state)) if self.tabs.is_empty() => {
+                            // Positions mean nothing yet, keep the newest
+                            // state until the tabs are known, along with the
+                            // tabs of the first answer
+                            self.pending_sync = Some(match self.pending_sync.take() {
+                                Some((writer, pending))
+                                    if (pending.version, writer) > (state.version, source_plugin_id) =>
+                                {
+                                    (writer, SyncState { tabs: pending.tabs.or(state.tabs), ..pending })
                                 }
-                            }
+                                Some((_, pending)) => {
+                                    (source_plugin_id, SyncState { tabs: pending.tabs.or(state.tabs), ..state })
+                                }
+                                None => (source_plugin_id, state),
+                            });
                         }
+                        Some(Ok(state)) => should_render = self.adopt_state(source_plugin_id, state),
+                        Some(Err(e)) => eprintln!("[zj-status-sidebar] Invalid sidebar state: {}", e),
+                        None => {}
                     }
+                } else if pipe_message.name == sync::REQUEST_MESSAGE
+                    && (self.sync.version() > 0
+                        || !self.tab_alerts.is_empty()
+                        || !self.tab_meta.is_empty()
+                        || !self.name_cache.is_empty())
+                {
+                    // A new sidebar wants to catch up, only those with
+                    // something to tell answer. Alerts are brought up to
+                    // now first, the newcomer starts their clock on arrival.
+                    self.advance_alerts();
+                    let state = SyncState {
+                        tabs: Some(self.synced_tabs()),
+                        ..self.sync_state(self.sync.version())
+                    };
+                    self.emit(HostCommand::MessageToPlugin(
+                        Some(source_plugin_id),
+                        sync::STATE_MESSAGE.to_string(),
+                        serde_json::to_string(&state).unwrap_or_default(),
+                    ));
                 }
             }
         }
         should_render
     }
 
-    fn render(&mut self, rows: usize, cols: usize) {
-        if self.tabs.is_empty() || rows == 0 || cols == 0 {
-            return;
+    // The id other sidebars see this instance by, the simulator has none
+    fn own_plugin_id(&self) -> u32 {
+        self.plugin_id.unwrap_or(0)
+    }
+
+    fn sync_state(&self, version: u64) -> SyncState {
+        SyncState {
+            version,
+            tabs: None,
+            collapsed: self.collapsed,
+            scroll_offset: self.tab_list_layout().scroll_offset(),
         }
-        
-        // Update dimensions
-        self.cols = cols;
-        self.rows = rows;
-        
-        let background = self.mode_info.style.colors.ribbon_unselected.background;
-        let text_color = self.mode_info.style.colors.ribbon_unselected.base;
-        
-        print!("\x1b[2J");
-        
-        // Row 1: Title
-        print!("\x1b[1;1H");
-        let title = "SIDEBAR V3";
-        let title_line = style!(text_color, background)
-            .bold()
-            .paint(format!("{:^width$}", title, width = cols));
-        print!("{}", title_line);
-        
-        // Row 2: Spacer
-        print!("\x1b[2;1H");
-        let empty_line = style!(text_color, background)
-            .paint(format!("{:width$}", "", width = cols));
-        print!("{}", empty_line);
-        
-        // Tabs
-        let tab_height = 3;
-        let mut current_row = 3;
-        
-        for (_idx, t) in self.tabs.iter().enumerate() {
-            if current_row + tab_height - 1 > rows {
-                break;
-            }
-            
-            let generated_name = self.name_cache.get_or_generate(t.position).to_string();
-            let emoji = generated_name.split_whitespace().next().unwrap_or("📄").to_string();
-            
-            let display_name = if !t.name.is_empty() && !t.name.starts_with("Tab ") {
-                format!("{} {}", emoji, t.name)
-            } else {
-                generated_name.clone()
-            };
-            
-            // Show rename indicator if this is the active tab and we're in rename mode
-            let is_renaming = t.active && self.mode_info.mode == InputMode::RenameTab;
-            let mut display_name_with_indicator = if is_renaming {
-                // Replace the emoji with pencil, keep the rest of the name
-                let name_parts: Vec<&str> = display_name.splitn(2, ' ').collect();
-                if name_parts.len() > 1 {
-                    format!("✏️ {}", name_parts[1])
-                } else {
-                    format!("✏️ {}", display_name)
-                }
-            } else {
-                display_name
-            };
-            
-            let (fg_color, bg_color) = if t.active {
-                (background, text_color)
-            } else {
-                (text_color, background)
-            };
-            
-            let alert_info = self.tab_alerts.get(&t.position);
-            let (final_fg, final_bg, notification_indicator) = if let Some(alert) = alert_info {
-                match &alert.alert_type {
-                    AlertType::CommandResult { success } => {
-                        let alert_color = if *success {
-                            self.mode_info.style.colors.frame_highlight.background
-                        } else {
-                            self.mode_info.style.colors.frame_unselected.unwrap_or_default().background
-                        };
-                        let (fg, bg) = if alert.alternate_color {
-                            (fg_color, alert_color)
-                        } else {
-                            (alert_color, bg_color)
-                        };
-                        (fg, bg, None)
-                    }
-                    AlertType::Notification => {
-                        // Red color for notifications
-                        let red_color = self.mode_info.style.colors.frame_unselected.unwrap_or_default().background;
-                        let (fg, bg) = if alert.alternate_color || alert.flash_count == 0 {
-                            (fg_color, red_color)
-                        } else {
-                            (fg_color, bg_color)
-                        };
-                        (fg, bg, Some("🔴"))
-                    }
+    }
+
+    // What a late sidebar missed, see sync.rs
+    fn synced_tabs(&self) -> Vec<SyncedTab> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .filter_map(|(position, t)| {
+                let tab_id = self.tab_ids.id_at(position)?;
+                let alerts = self.tab_alerts.get(&tab_id).cloned().unwrap_or_default();
+                let meta = self.tab_meta.get(&tab_id).cloned().unwrap_or_default();
+                let generated_name = self.name_cache.get(tab_id).map(str::to_string);
+                if alerts.is_empty() && meta.is_empty() && generated_name.is_none() {
+                    return None;
                 }
-            } else {
-                (fg_color, bg_color, None)
+                Some(SyncedTab {
+                    position,
+                    name: t.name.clone(),
+                    alerts,
+                    meta,
+                    generated_name,
+                })
+            })
+            .collect()
+    }
+
+    // Something only this instance saw changed, tell the other sidebars
+    fn publish_state(&mut self) {
+        let version = self.sync.bump(self.own_plugin_id());
+        let state = self.sync_state(version);
+        self.emit(HostCommand::MessageToPlugin(
+            None,
+            sync::STATE_MESSAGE.to_string(),
+            serde_json::to_string(&state).unwrap_or_default(),
+        ));
+    }
+
+    // Take over another sidebar's view state when it's newer than ours, and
+    // the tabs of the first catch-up answer. Returns whether anything changed.
+    fn adopt_state(&mut self, writer: u32, state: SyncState) -> bool {
+        let mut changed = false;
+        if let Some(tabs) = state.tabs.filter(|_| !self.caught_up) {
+            self.caught_up = true;
+            changed |= self.adopt_tabs(tabs);
+        }
+        if self.sync.observe(state.version, writer) {
+            let scroll_offset = self.tab_list_layout().scroll_offset();
+            changed |= self.collapsed != state.collapsed || scroll_offset != state.scroll_offset;
+            self.collapsed = state.collapsed;
+            self.scroll_offset = state.scroll_offset;
+            self.scroll_to_active = false;
+        }
+        changed
+    }
+
+    // Tabs are matched by position and name, entries for tabs this instance
+    // doesn't see the same way are dropped
+    fn adopt_tabs(&mut self, tabs: Vec<SyncedTab>) -> bool {
+        let mut tab_alerts = HashMap::new();
+        let mut tab_meta = HashMap::new();
+        let mut names_changed = false;
+        for synced in tabs {
+            let seen_alike = self.tabs.get(synced.position).is_some_and(|t| t.name == synced.name);
+            let Some(tab_id) = self.tab_ids.id_at(synced.position).filter(|_| seen_alike) else {
+                continue;
             };
-            
-            // Add notification indicator to the display name
-            if let Some(indicator) = notification_indicator {
-                // Prepend the indicator
-                display_name_with_indicator = format!("{} {}", indicator, display_name_with_indicator);
+            if !synced.alerts.is_empty() {
+                tab_alerts.insert(tab_id, synced.alerts);
             }
-            
-            for row_offset in 0..tab_height {
-                print!("\x1b[{};1H", current_row + row_offset);
-                
-                let content = if row_offset == 0 || row_offset == 2 {
-                    String::from("")
-                } else if row_offset == 1 {
-                    // Display emoji + name with left padding
-                    format!(" {}", display_name_with_indicator)
-                } else {
-                    String::from("")
-                };
-                
-                let formatted_content = safe_truncate_to_width(&content, cols);
-                
-                let tab_line = if t.active {
-                    style!(final_fg, final_bg)
-                        .bold()
-                        .paint(&formatted_content)
-                } else {
-                    style!(final_fg, final_bg)
-                        .paint(&formatted_content)
-                };
-                
-                print!("{}", tab_line);
+            if !synced.meta.is_empty() {
+                tab_meta.insert(tab_id, synced.meta);
+            }
+            // An instance that started late may not have seen the pane a
+            // tab's name was generated from
+            if let Some(name) = synced.generated_name {
+                if self.name_cache.get(tab_id) != Some(name.as_str()) {
+                    self.name_cache.set(tab_id, name);
+                    names_changed = true;
+                }
             }
-            
-            current_row += tab_height;
         }
-        
-        // Fill remaining
-        while current_row <= rows {
-            print!("\x1b[{};1H", current_row);
-            let empty_line = style!(text_color, background)
-                .paint(format!("{:width$}", "", width = cols));
-            print!("{}", empty_line);
-            current_row += 1;
+        let changed = names_changed || self.tab_alerts != tab_alerts || self.tab_meta != tab_meta;
+        self.tab_alerts = tab_alerts;
+        self.tab_meta = tab_meta;
+        self.alerts_ticked_at = self.scheduler.now();
+        // The sender may not have seen the latest focus change yet
+        if let Some(active_tab_index) = self.active_tab_idx.checked_sub(1) {
+            self.clear_seen_alerts(active_tab_index);
         }
-        
-        use std::io::{self, Write};
-        let _ = io::stdout().flush();
+        self.schedule_alerts();
+        changed
     }
-}
 
-fn safe_truncate_to_width(s: &str, max_width: usize) -> String {
-    if max_width == 0 {
-        return String::new();
-    }
-    
-    let display_width = s.width();
-    
-    if display_width <= max_width {
-        // For single emojis or very short strings, always center them
-        if display_width <= 2 {
-            // Center the emoji
-            let padding_total = max_width.saturating_sub(display_width);
-            let padding_left = padding_total / 2;
-            let padding_right = padding_total - padding_left;
-            
-            // Debug log for centering
-            if s.chars().any(|c| c > '\u{1F000}') {  // Likely an emoji
-                eprintln!("[zj-status-sidebar] Centering '{}': width={}, max_width={}, pad_left={}, pad_right={}", 
-                         s, display_width, max_width, padding_left, padding_right);
-            }
-            
-            let mut result = " ".repeat(padding_left);
-            result.push_str(s);
-            result.push_str(&" ".repeat(padding_right));
-            return result;
-        }
-        return format!("{:width$}", s, width = max_width);
-    }
-    
-    if max_width <= 3 {
-        let mut result = String::new();
-        let mut current_width = 0;
-        
-        for ch in s.chars() {
-            let ch_width = ch.width().unwrap_or(0);
-            if current_width + ch_width <= max_width {
-                result.push(ch);
-                current_width += ch_width;
-            } else {
-                break;
+    // A tab as the sidebar shows it, for places that name it in text
+    fn tab_name(&self, idx: usize, tab_id: TabId) -> String {
+        if idx >= self.tabs.len() {
+            return String::new();
+        }
+        match self.tab_label(idx, tab_id, false) {
+            (Some(emoji), name) => format!("{} {}", emoji, name),
+            (None, name) => name,
+        }
+    }
+
+    fn record_history(&mut self, idx: usize, tab_id: TabId, alert: &TabAlert) {
+        let result = match &alert.alert_type {
+            AlertType::CommandResult(result) => Some(result),
+            AlertType::Notification => None,
+        };
+        self.history.push(HistoryEntry {
+            at: self.scheduler.unix_time(),
+            tab_id,
+            tab: idx + 1,
+            tab_name: self.tab_name(idx, tab_id),
+            kind: EntryKind::of(&alert.alert_type),
+            severity: alert.notice.severity,
+            message: alert.notice.text(),
+            exit_code: result.map(|r| r.exit_code),
+            command: result.and_then(|r| r.command.clone()),
+            duration_ms: result.and_then(|r| r.duration_ms),
+            unread: true,
+        });
+    }
+
+    // Alert on command panes that finished on other tabs, when auto alerts
+    // are configured for them. Returns whether any alert was raised.
+    fn alert_finished_commands(&mut self) -> bool {
+        if !self.config.auto_alerts() {
+            return false;
+        }
+        let finished = self.command_watch.update(&self.pane_info, self.scheduler.now());
+        let mut raised = false;
+        for (idx, pane_id, result) in finished {
+            let (Some(t), Some(tab_id)) = (self.tabs.get(idx), self.tab_ids.id_at(idx)) else {
+                continue;
+            };
+            let command = result.command.as_deref().unwrap_or_default();
+            if !self.config.auto_alerts_on(&t.name, command) {
+                continue;
             }
+            let alert = TabAlert::new(AlertType::CommandResult(result), self.config.command_alert);
+            raised |= self.alert_tab(idx, tab_id, Some(pane_id), alert);
         }
-        
-        while current_width < max_width {
-            result.push(' ');
-            current_width += 1;
+        raised
+    }
+
+    // Notify the tabs of panes whose title changed into one a `title_alert`
+    // rule matches. Returns whether any alert was raised.
+    fn alert_title_changes(&mut self) -> bool {
+        if self.config.title_rules.is_empty() {
+            return false;
         }
-        
-        return result;
-    }
-    
-    let target_width = max_width.saturating_sub(3);
-    let mut result = String::new();
-    let mut current_width = 0;
-    
-    for ch in s.chars() {
-        let ch_width = ch.width().unwrap_or(0);
-        if current_width + ch_width <= target_width {
-            result.push(ch);
-            current_width += ch_width;
-        } else {
-            break;
+        let mut raised = false;
+        for (idx, pane_id, title) in self.title_watch.update(&self.pane_info) {
+            let Some(rule) = titles::matching_rule(&self.config.title_rules, &title) else {
+                continue;
+            };
+            let Some(tab_id) = self.tab_ids.id_at(idx) else {
+                continue;
+            };
+            let alert = TabAlert {
+                notice: Notice {
+                    severity: rule.severity,
+                    message: Some(title),
+                    source: None,
+                },
+                ..TabAlert::new(AlertType::Notification, self.config.notification_alert)
+            };
+            raised |= self.alert_tab(idx, tab_id, Some(pane_id), alert);
+        }
+        raised
+    }
+
+    // Alert a tab, about one of its panes when known. The active tab gets no
+    // alert unless it's about a pane that isn't focused (see pane_alerts).
+    // Returns whether the alert was raised.
+    fn alert_tab(&mut self, idx: usize, tab_id: TabId, pane_id: Option<u32>, alert: TabAlert) -> bool {
+        let pane_id = pane_id.and_then(|pane_id| self.unfocused_pane(idx, pane_id));
+        if idx + 1 == self.active_tab_idx && pane_id.is_none() {
+            return false;
         }
+        let alert = TabAlert { pane_id, ..alert };
+        self.record_history(idx, tab_id, &alert);
+        // Bring the running alerts up to date first so the new one doesn't
+        // inherit time it wasn't there for
+        self.advance_alerts();
+        self.tab_alerts.entry(tab_id).or_default().push(alert);
+        self.schedule_alerts();
+        true
     }
-    
-    result.push_str("...");
-    
-    let final_width = result.width();
-    if final_width < max_width {
-        for _ in final_width..max_width {
-            result.push(' ');
+
+    // Blink and expire alerts by the time passed since they were last ticked
+    fn advance_alerts(&mut self) {
+        let now = self.scheduler.now();
+        let elapsed = now - self.alerts_ticked_at;
+        self.alerts_ticked_at = now;
+        if elapsed > 0.0 {
+            self.tab_alerts.retain(|_, queue| queue.tick(elapsed));
+        }
+    }
+
+    // Make sure a timer fires when the soonest alert needs to blink or expire
+    fn schedule_alerts(&mut self) {
+        let next_tick = self
+            .tab_alerts
+            .values()
+            .filter_map(|queue| queue.next_tick())
+            .min_by(|a, b| a.total_cmp(b));
+        if let Some(delay) = next_tick.and_then(|delay| self.scheduler.wake_in(delay)) {
+            self.emit(HostCommand::SetTimeout(delay));
         }
     }
-    
-    result
-}
\ No newline at end of file
+
+    fn emit(&mut self, command: HostCommand) {
+        self.commands.push(command);
+    }
+
+    fn run_commands(&mut self) {
+        for command in self.commands.drain(..) {
+            command.run();
+        }
+    }
+}
+
+impl ZellijPlugin for State {
+    fn load(&mut self, configuration: BTreeMap<String, String>) {
+        self.plugin_id = Some(get_plugin_ids().plugin_id);
+        self.handle_load(configuration);
+        self.run_commands();
+    }
+
+    fn update(&mut self, event: Event) -> bool {
+        let should_render = self.handle_event(event);
+        self.run_commands();
+        should_render
+    }
+
+    fn pipe(&mut self, pipe_message: PipeMessage) -> bool {
+        let should_render = self.handle_pipe(pipe_message);
+        self.run_commands();
+        should_render
+    }
+
+    fn render(&mut self, rows: usize, cols: usize) {
+        if self.tabs.is_empty() || rows == 0 || cols == 0 {
+            return;
+        }
+        
+        self.rows = rows;
+        self.cols = cols;
+        let frame = self.draw();
+        print!("{}", frame.to_ansi());
+        
+        use std::io::{self, Write};
+        let _ = io::stdout().flush();
+    }
+}
@@ -2,6 +2,8 @@
 
 use std::collections::HashMap;
 
+use crate::identity::TabId;
+
 // Arrays of emojis, adjectives, and nouns
 const EMOJIS: &[&str] = &[
     "🌟", "🚀", "🎨", "🌈", "⚡", "🔥", "❄️", "🌸", "🍀", "🦄",
@@ -46,13 +48,13 @@ fn simple_hash(seed: u64) -> u64 {
     x ^ (x >> 31)
 }
 
-pub fn generate_tab_name(tab_index: usize, session_seed: u64) -> String {
-    // For unique emojis, use tab_index modulo emoji count
-    // This ensures each tab gets a unique emoji up to the number of available emojis
-    let emoji_idx = tab_index % EMOJIS.len();
+// Seeded by a pane id rather than the tab's position or TabId, which differ
+// between sidebar instances, so every instance comes up with the same name
+pub fn generate_tab_name(pane_id: u32, session_seed: u64) -> String {
+    let emoji_idx = pane_id as usize % EMOJIS.len();
     
-    // Mix the tab index with session seed for random adjective/noun
-    let seed = simple_hash(session_seed.wrapping_add(tab_index as u64));
+    // Mix the pane id with session seed for random adjective/noun
+    let seed = simple_hash(session_seed.wrapping_add(pane_id as u64));
     let adj_idx = (simple_hash(seed) % ADJECTIVES.len() as u64) as usize;
     let noun_idx = (simple_hash(simple_hash(seed)) % NOUNS.len() as u64) as usize;
     
@@ -65,7 +67,7 @@ pub fn generate_tab_name(tab_index: usize, session_seed: u64) -> String {
 
 // Cache generated names to ensure consistency
 pub struct NameCache {
-    names: HashMap<usize, String>,
+    names: HashMap<TabId, String>,
     session_seed: u64,
 }
 
@@ -85,13 +87,40 @@ impl NameCache {
             seed = seed.wrapping_add((byte as u64).wrapping_mul((i + 1) as u64));
             seed = simple_hash(seed);
         }
-        self.session_seed = seed;
-        // Clear any cached names to regenerate with new seed
-        self.names.clear();
+        if seed != self.session_seed {
+            self.session_seed = seed;
+            // Clear any cached names to regenerate with new seed
+            self.names.clear();
+        }
+    }
+    
+    // Keyed by the tab's stable id so a name sticks to its tab when others
+    // are closed or moved, and named after the tab's first pane so it sticks
+    // when that pane is closed. Returns whether a name was added, a tab
+    // keeps the name it has.
+    pub fn generate(&mut self, tab_id: TabId, first_pane_id: u32) -> bool {
+        if self.names.contains_key(&tab_id) {
+            return false;
+        }
+        self.names.insert(tab_id, generate_tab_name(first_pane_id, self.session_seed));
+        true
+    }
+    
+    pub fn get(&self, tab_id: TabId) -> Option<&str> {
+        self.names.get(&tab_id).map(String::as_str)
+    }
+    
+    // A name another sidebar generated, for tabs whose first pane this
+    // instance never saw
+    pub fn set(&mut self, tab_id: TabId, name: String) {
+        self.names.insert(tab_id, name);
     }
     
-    pub fn get_or_generate(&mut self, tab_index: usize) -> &str {
-        self.names.entry(tab_index)
-            .or_insert_with(|| generate_tab_name(tab_index, self.session_seed))
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
     }
-}
\ No newline at end of file
+    
+    pub fn forget(&mut self, tab_id: TabId) {
+        self.names.remove(&tab_id);
+    }
+}
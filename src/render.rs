@@ -0,0 +1,667 @@
+// Pure rendering: State is drawn into a Frame of styled lines without
+// touching stdout, so layout, truncation and highlighting can be tested on
+// the host. `ZellijPlugin::render` only emits the finished frame.
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use zellij_tile::prelude::*;
+use zellij_tile_utils::style;
+
+use crate::alert::{AlertType, Icons, TabAlert};
+use crate::identity::TabId;
+use crate::theme::Theme;
+use crate::{line, tab, State};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellStyle {
+    pub fg: PaletteColor,
+    pub bg: PaletteColor,
+    pub bold: bool,
+}
+
+impl CellStyle {
+    pub fn new(fg: PaletteColor, bg: PaletteColor) -> Self {
+        Self { fg, bg, bold: false }
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub style: CellStyle,
+}
+
+// A rows x cols grid, each line is a list of styled spans whose widths add
+// up to exactly `cols`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    cols: usize,
+    lines: Vec<Vec<Span>>,
+}
+
+impl Frame {
+    pub fn new(rows: usize, cols: usize, style: CellStyle) -> Self {
+        let blank = vec![Span { text: " ".repeat(cols), style }];
+        Self {
+            cols,
+            lines: vec![blank; rows],
+        }
+    }
+
+    // Replace a whole line, padding or truncating the text to the width
+    pub fn set_line(&mut self, row: usize, text: &str, style: CellStyle) {
+        if let Some(line) = self.lines.get_mut(row) {
+            *line = vec![Span {
+                text: safe_truncate_to_width(text, self.cols),
+                style,
+            }];
+        }
+    }
+
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::from("\x1b[2J");
+        for (row, spans) in self.lines.iter().enumerate() {
+            out.push_str(&format!("\x1b[{};1H", row + 1));
+            for span in spans {
+                let ansi_style = if span.style.bold {
+                    style!(span.style.fg, span.style.bg).bold()
+                } else {
+                    style!(span.style.fg, span.style.bg)
+                };
+                out.push_str(&ansi_style.paint(span.text.as_str()).to_string());
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+impl Frame {
+    pub fn text(&self, row: usize) -> String {
+        self.lines
+            .get(row)
+            .map(|spans| spans.iter().map(|s| s.text.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    // Text of every line with trailing padding removed, handy for snapshots
+    pub fn text_lines(&self) -> Vec<String> {
+        (0..self.lines.len())
+            .map(|row| self.text(row).trim_end().to_string())
+            .collect()
+    }
+
+    // Style of the first span of a line
+    pub fn style(&self, row: usize) -> Option<CellStyle> {
+        self.lines.get(row).and_then(|spans| spans.first()).map(|s| s.style)
+    }
+}
+
+impl State {
+    // Draw at the size of the last render, see ZellijPlugin::render
+    pub(crate) fn draw(&self) -> Frame {
+        let (rows, cols) = (self.rows, self.cols);
+        let theme = Theme::new(self.config.theme, &self.config.colors, &self.mode_info.style.colors);
+        let background = theme.background;
+        let base_style = CellStyle::new(theme.text, background);
+
+        let mut frame = Frame::new(rows, cols, base_style);
+
+        // Row 1: Header with session and mode (click to toggle collapsed/expanded)
+        let mode = self.mode_info.mode;
+        let title = if self.collapsed {
+            line::collapsed_header_text(mode)
+        } else {
+            line::header_text(&self.config.title, self.mode_info.session_name.as_deref(), mode)
+        };
+        let header_color = theme
+            .header
+            .unwrap_or_else(|| line::mode_color(mode, &self.mode_info.style.colors));
+        frame.set_line(0, &center_to_width(&title, cols), CellStyle::new(background, header_color).bold());
+
+        // Config errors, shown until the layout is fixed
+        if let Some(error) = self.config_error_line() {
+            frame.set_line(1, &error, CellStyle::new(theme.failure, background));
+        }
+
+        if self.showing_history() {
+            self.draw_history(&mut frame, base_style);
+        } else {
+            self.draw_tab_list(&mut frame, &theme);
+        }
+
+        // Keybinding hints footer
+        let footer_lines = self.footer_lines();
+        if !footer_lines.is_empty() {
+            let footer_top = rows - footer_lines.len() - 1;
+            frame.set_line(footer_top, &"─".repeat(cols), CellStyle::new(theme.separator, background));
+            for (i, hint) in footer_lines.iter().enumerate() {
+                frame.set_line(footer_top + i + 1, hint, base_style);
+            }
+        }
+
+        frame
+    }
+
+    fn draw_tab_list(&self, frame: &mut Frame, theme: &Theme) {
+        let cols = self.cols;
+        let background = theme.background;
+        let base_style = CellStyle::new(theme.text, background);
+
+        let layout = self.tab_list_layout();
+
+        for idx in layout.visible_entries() {
+            let t = &self.tabs[idx];
+            let Some(tab_id) = self.tab_ids.id_at(idx) else {
+                continue;
+            };
+            let meta = self.tab_meta.get(&tab_id);
+            let is_renaming = t.active && self.mode_info.mode == InputMode::RenameTab;
+            let (emoji, name) = self.tab_label(idx, tab_id, is_renaming);
+
+            // Show rename indicator if this is the active tab and we're in rename mode
+            let mut display_name_with_indicator = if is_renaming {
+                // Replace the emoji with pencil, keep the rest of the name
+                format!("✏️ {}", name)
+            } else if let Some(emoji) = &emoji {
+                format!("{} {}", emoji, name)
+            } else {
+                name
+            };
+
+            let (fg_color, bg_color) = match meta.and_then(|m| m.color) {
+                Some(tab_color) if t.active => (background, tab_color),
+                Some(tab_color) => (tab_color, background),
+                None if t.active => (background, theme.active_tab),
+                None => (theme.inactive_tab, background),
+            };
+
+            // Alerts about a pane in the tree are drawn on that pane's row
+            // instead of the entry
+            let panes = self.pane_tree(t);
+            let queue = self.tab_alerts.get(&tab_id);
+            let shown_panes: Vec<u32> = panes.iter().map(|p| p.id).collect();
+            let alert_info = queue.and_then(|queue| queue.latest_for_tab(&shown_panes));
+            // Detail row: result counts when several alerts are queued, then
+            // what the latest one is about
+            let details: Vec<String> = [
+                queue.filter(|queue| queue.len() > 1).and_then(|queue| queue.summary()),
+                alert_info.and_then(|alert| match &alert.alert_type {
+                    AlertType::CommandResult(result) => Some(result.details()),
+                    AlertType::Notification => alert.notice.text(),
+                }),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            let (final_fg, final_bg, alert_indicator) = match alert_info {
+                Some(alert) => alert_colors(theme, self.config.alert_icons, alert, fg_color, bg_color),
+                None => (fg_color, bg_color, None),
+            };
+
+            if self.collapsed {
+                // Collapsed mode only has room for a single glyph per tab, the
+                // alert and rename indicators take the emoji's place
+                display_name_with_indicator = if let Some(indicator) = alert_indicator {
+                    indicator.to_string()
+                } else if is_renaming {
+                    "✏️".to_string()
+                } else {
+                    match emoji {
+                        Some(emoji) if cols >= 2 => emoji,
+                        _ => (t.position + 1).to_string(),
+                    }
+                };
+            } else {
+                if let Some(indicator) = alert_indicator {
+                    // Prepend the indicator
+                    display_name_with_indicator = format!("{} {}", indicator, display_name_with_indicator);
+                }
+                if let Some(queue) = queue.filter(|queue| queue.len() > 1) {
+                    // Badge with the number of alerts waiting on the tab
+                    display_name_with_indicator.push_str(&format!(" ({})", queue.len()));
+                }
+                display_name_with_indicator.push_str(&tab::status_glyphs(t, &self.config));
+            }
+
+            let tab_style = if t.active {
+                CellStyle::new(final_fg, final_bg).bold()
+            } else {
+                CellStyle::new(final_fg, final_bg)
+            };
+            for (row_offset, row) in layout.entry_rows(idx).enumerate() {
+                let content = if row_offset == layout.label_offset() {
+                    if self.collapsed {
+                        // Emoji only, centered by safe_truncate_to_width
+                        display_name_with_indicator.clone()
+                    } else {
+                        // Display emoji + name with left padding
+                        format!(" {}", display_name_with_indicator)
+                    }
+                } else if row_offset == layout.label_offset() + 1 && !self.collapsed {
+                    // Alert details under the name, when the entry has a row
+                    // to spare
+                    if details.is_empty() {
+                        String::new()
+                    } else {
+                        format!("    {}", details.join(" · "))
+                    }
+                } else {
+                    String::from("")
+                };
+                frame.set_line(row, &content, tab_style);
+            }
+
+            // Pane tree, drawn in the sidebar colors so it reads as part of
+            // the entry above without looking like another tab
+            for (i, row) in layout.child_rows(idx).enumerate() {
+                let pane = panes[i];
+                let mut content = tab::pane_label(pane, i + 1 == panes.len());
+                let pane_alert = queue.and_then(|queue| queue.latest_for_pane(pane.id));
+                let (fg, bg, indicator) = match pane_alert {
+                    Some(alert) => alert_colors(theme, self.config.alert_icons, alert, base_style.fg, base_style.bg),
+                    None => (base_style.fg, base_style.bg, None),
+                };
+                if let Some(indicator) = indicator {
+                    content = format!("{} {}", content, indicator);
+                }
+                let pane_style = if pane.is_focused && t.active {
+                    CellStyle::new(fg, bg).bold()
+                } else {
+                    CellStyle::new(fg, bg)
+                };
+                frame.set_line(row, &content, pane_style);
+            }
+        }
+
+        // Overflow indicators, the row above the entries is blank when
+        // scrolled to the top
+        if layout.overflows() {
+            let indicator = |arrow: &str, hidden: usize| {
+                if self.collapsed {
+                    format!("{}{}", arrow, hidden)
+                } else {
+                    format!(" {} {} more", arrow, hidden)
+                }
+            };
+            if let Some((row, hidden)) = layout.more_above() {
+                frame.set_line(row, &indicator("▲", hidden), base_style);
+            }
+            if let Some((row, hidden)) = layout.more_below() {
+                frame.set_line(row, &indicator("▼", hidden), base_style);
+            }
+        }
+    }
+
+    // Emoji and name a tab is shown with. User-assigned meta wins over the
+    // generated emoji and name, a name the user gave the tab over both.
+    pub(crate) fn tab_label(&self, idx: usize, tab_id: TabId, is_renaming: bool) -> (Option<String>, String) {
+        let t = &self.tabs[idx];
+        let meta = self.tab_meta.get(&tab_id);
+        // Names are generated as the panes arrive, see State::generate_names
+        let generated = if self.config.generate_names {
+            self.name_cache.get(tab_id)
+        } else {
+            None
+        };
+        let (generated_emoji, generated_name) = match generated {
+            Some(generated) => {
+                let (emoji, name) = generated.split_once(' ').unwrap_or(("📄", generated));
+                (Some(emoji.to_string()), name.to_string())
+            }
+            None => (None, t.name.clone()),
+        };
+
+        let emoji = meta.and_then(|m| m.emoji.clone()).or(generated_emoji);
+        let name = match meta.and_then(|m| m.label.clone()) {
+            // The label would hide the name being typed while renaming
+            Some(label) if !is_renaming => label,
+            _ if !t.name.is_empty() && !t.name.starts_with("Tab ") => t.name.clone(),
+            _ => generated_name,
+        };
+        (emoji, name)
+    }
+
+    fn draw_history(&self, frame: &mut Frame, base_style: CellStyle) {
+        let top = self.first_tab_row();
+        let bottom = self.rows.saturating_sub(self.footer_height());
+        let title = match self.history.unread_count() {
+            0 => String::from(" History"),
+            unread => format!(" History · {} unread", unread),
+        };
+        frame.set_line(top, &title, base_style.bold());
+
+        let now = self.scheduler.unix_time();
+        let rows = (top + 1..bottom).zip(self.history.entries());
+        for (row, entry) in rows {
+            let style = if entry.unread { base_style.bold() } else { base_style };
+            frame.set_line(row, &format!(" {}", entry.summary(now, self.config.alert_icons)), style);
+        }
+        if self.history.entries().next().is_none() && top + 1 < bottom {
+            frame.set_line(top + 1, " Nothing yet", base_style);
+        }
+    }
+}
+
+// Colors for something with an alert on it, plus the glyph to show next to
+// it so the alert doesn't rely on color alone
+fn alert_colors(
+    theme: &Theme,
+    icons: Icons,
+    alert: &TabAlert,
+    fg: PaletteColor,
+    bg: PaletteColor,
+) -> (PaletteColor, PaletteColor, Option<&'static str>) {
+    match &alert.alert_type {
+        AlertType::CommandResult(result) => {
+            let (alert_color, glyph) = if result.success() {
+                (theme.success, "✓")
+            } else {
+                (theme.failure, "✗")
+            };
+            if alert.is_lit() {
+                (fg, alert_color, Some(glyph))
+            } else {
+                (alert_color, bg, Some(glyph))
+            }
+        }
+        AlertType::Notification => {
+            let alert_color = theme.severity(alert.notice.severity);
+            let bg = if alert.is_lit() { alert_color } else { bg };
+            (fg, bg, Some(alert.notice.icon(icons)))
+        }
+    }
+}
+
+// Center by display width, `{:^}` pads by chars and so pushes wide session
+// names off the end
+fn center_to_width(s: &str, width: usize) -> String {
+    let padding = width.saturating_sub(s.width());
+    let left = padding / 2;
+    format!("{}{}{}", " ".repeat(left), s, " ".repeat(padding - left))
+}
+
+pub fn safe_truncate_to_width(s: &str, max_width: usize) -> String {
+    if max_width == 0 {
+        return String::new();
+    }
+    
+    let display_width = s.width();
+    
+    if display_width <= max_width {
+        // For single emojis or very short strings, always center them
+        if display_width <= 2 {
+            // Center the emoji
+            let padding_total = max_width.saturating_sub(display_width);
+            let padding_left = padding_total / 2;
+            let padding_right = padding_total - padding_left;
+            
+            let mut result = " ".repeat(padding_left);
+            result.push_str(s);
+            result.push_str(&" ".repeat(padding_right));
+            return result;
+        }
+        // Pad by display width, emojis take two columns but count as one char
+        return format!("{}{}", s, " ".repeat(max_width - display_width));
+    }
+    
+    if max_width <= 3 {
+        let mut result = String::new();
+        let mut current_width = 0;
+        
+        for ch in s.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+            if current_width + ch_width <= max_width {
+                result.push(ch);
+                current_width += ch_width;
+            } else {
+                break;
+            }
+        }
+        
+        while current_width < max_width {
+            result.push(' ');
+            current_width += 1;
+        }
+        
+        return result;
+    }
+    
+    let target_width = max_width.saturating_sub(3);
+    let mut result = String::new();
+    let mut current_width = 0;
+    
+    for ch in s.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if current_width + ch_width <= target_width {
+            result.push(ch);
+            current_width += ch_width;
+        } else {
+            break;
+        }
+    }
+    
+    result.push_str("...");
+    
+    let final_width = result.width();
+    if final_width < max_width {
+        for _ in final_width..max_width {
+            result.push(' ');
+        }
+    }
+    
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alert::{AlertPolicy, CommandResult, Notice, Severity, TabAlert};
+    use crate::config::Config;
+    use crate::sim::{one_pane_per_tab, pane, tab_infos};
+
+    const FG: PaletteColor = PaletteColor::EightBit(7);
+    const BG: PaletteColor = PaletteColor::EightBit(0);
+
+    // The first tab is active
+    fn state_with_tabs(names: &[impl AsRef<str>]) -> State {
+        let mut state = State::default();
+        state.mode_info.style.colors.ribbon_unselected.base = FG;
+        state.mode_info.style.colors.ribbon_unselected.background = BG;
+        // Generated names come from each tab's first pane, a hidden one
+        // here so no pane tree is drawn
+        let panes = (0..names.len()).map(|position| pane(position as u32));
+        state.handle_event(Event::TabUpdate(tab_infos(names, 0)));
+        state.handle_event(Event::PaneUpdate(one_pane_per_tab(panes)));
+        state
+    }
+
+    // Draw at the given size, as ZellijPlugin::render does
+    fn draw(state: &mut State, rows: usize, cols: usize) -> Frame {
+        state.rows = rows;
+        state.cols = cols;
+        state.draw()
+    }
+
+    fn two_tabs() -> State {
+        state_with_tabs(&["editor", "server"])
+    }
+
+    #[test]
+    fn draws_header_and_tab_labels() {
+        let frame = draw(&mut two_tabs(), 10, 20);
+        assert_eq!(frame.text(0).trim(), "NORMAL");
+        assert_eq!(
+            frame.text_lines()[1..],
+            ["", "", " 🌟 editor", "", "", " 🚀 server", "", "", ""]
+        );
+    }
+
+    #[test]
+    fn wide_session_names_are_centered_by_display_width() {
+        let mut state = two_tabs();
+        state.mode_info.session_name = Some("日本🚀".to_string());
+        let frame = draw(&mut state, 10, 20);
+        assert_eq!(frame.text(0), "   NORMAL 日本🚀    ");
+    }
+
+    #[test]
+    fn config_errors_share_one_row() {
+        let mut state = two_tabs();
+        state.config.tab_height = 1;
+        let (_, errors) = Config::from_configuration(&(0..20).map(|i| (format!("typo{}", i), String::new())).collect());
+        state.config_errors = errors;
+        let frame = draw(&mut state, 10, 20);
+        assert_eq!(frame.text_lines()[1..5], ["⚠ 20 config errors", "", " 🌟 editor", " 🚀 server"]);
+
+        state.config_errors.truncate(1);
+        assert_eq!(draw(&mut state, 10, 20).text(1), "⚠ typo0: unknown key");
+    }
+
+    #[test]
+    fn every_line_fills_the_width() {
+        let frame = draw(&mut two_tabs(), 10, 20);
+        for row in 0..10 {
+            assert_eq!(frame.text(row).width(), 20, "row {}", row);
+        }
+    }
+
+    #[test]
+    fn active_tab_is_inverted_and_bold() {
+        let frame = draw(&mut two_tabs(), 10, 20);
+        assert_eq!(frame.style(3), Some(CellStyle::new(BG, FG).bold()));
+        assert_eq!(frame.style(6), Some(CellStyle::new(FG, BG)));
+    }
+
+    #[test]
+    fn long_names_are_truncated_to_the_width() {
+        let mut state = state_with_tabs(&["a-very-long-tab-name"]);
+        let frame = draw(&mut state, 5, 12);
+        assert_eq!(frame.text(3), " 🌟 a-ver...");
+        assert_eq!(frame.text(3).width(), 12);
+    }
+
+    #[test]
+    fn theme_preset_with_a_role_override() {
+        let mut state = two_tabs();
+        state.config.theme = crate::theme::Preset::Light;
+        state.config.colors.active_tab = Some(PaletteColor::EightBit(5));
+        let frame = draw(&mut state, 10, 20);
+        let (black, white) = (PaletteColor::EightBit(0), PaletteColor::EightBit(15));
+        assert_eq!(frame.style(3), Some(CellStyle::new(white, PaletteColor::EightBit(5)).bold()));
+        assert_eq!(frame.style(6), Some(CellStyle::new(black, white)));
+    }
+
+    #[test]
+    fn failure_defaults_to_the_theme_exit_code_error_color_with_a_glyph() {
+        let mut state = two_tabs();
+        state.mode_info.style.colors.exit_code_error.base = PaletteColor::EightBit(9);
+        state.tab_alerts.entry(1).or_default().push(
+            TabAlert::new(AlertType::CommandResult(CommandResult::exited(1)), AlertPolicy::command_result()),
+        );
+        let frame = draw(&mut state, 10, 20);
+        assert_eq!(frame.text_lines()[6], " ✗ 🚀 server");
+        assert_eq!(frame.style(6), Some(CellStyle::new(FG, PaletteColor::EightBit(9))));
+    }
+
+    #[test]
+    fn shape_icons_tell_severities_apart_without_color() {
+        let mut state = two_tabs();
+        state.config.alert_icons = Icons::Shapes;
+        state.tab_alerts.entry(1).or_default().push(TabAlert {
+            notice: Notice {
+                severity: Some(Severity::Warning),
+                ..Default::default()
+            },
+            ..TabAlert::new(AlertType::Notification, AlertPolicy::notification())
+        });
+        assert_eq!(draw(&mut state, 10, 20).text_lines()[6], " ⚠ 🚀 server");
+    }
+
+    #[test]
+    fn failed_command_alert_uses_failure_color() {
+        let mut state = two_tabs();
+        state.config.colors.failure = Some(PaletteColor::EightBit(1));
+        state.tab_alerts.entry(1).or_default().push(
+            TabAlert::new(AlertType::CommandResult(CommandResult::exited(1)), AlertPolicy::command_result()),
+        );
+        let frame = draw(&mut state, 10, 20);
+        assert_eq!(frame.style(6), Some(CellStyle::new(FG, PaletteColor::EightBit(1))));
+    }
+
+    #[test]
+    fn notification_prefixes_indicator() {
+        let mut state = two_tabs();
+        state.config.colors.notification = Some(PaletteColor::EightBit(2));
+        state.tab_alerts.entry(1).or_default().push(
+            TabAlert {
+                blinks_left: Some(0),
+                ..TabAlert::new(AlertType::Notification, AlertPolicy::notification())
+            },
+        );
+        let frame = draw(&mut state, 10, 20);
+        assert_eq!(frame.text_lines()[6], " 🔴 🚀 server");
+        assert_eq!(frame.style(6), Some(CellStyle::new(FG, PaletteColor::EightBit(2))));
+    }
+
+    #[test]
+    fn notification_message_goes_under_the_name() {
+        let mut state = two_tabs();
+        state.config.colors.warning = Some(PaletteColor::EightBit(3));
+        state.tab_alerts.entry(1).or_default().push(
+            TabAlert {
+                blinks_left: Some(0),
+                notice: Notice {
+                    severity: Some(Severity::Warning),
+                    message: Some("disk almost full".to_string()),
+                    source: None,
+                },
+                ..TabAlert::new(AlertType::Notification, AlertPolicy::notification())
+            },
+        );
+        let frame = draw(&mut state, 10, 24);
+        assert_eq!(frame.text_lines()[6..8], [" 🟡 🚀 server", "    disk almost full"]);
+        assert_eq!(frame.style(6), Some(CellStyle::new(FG, PaletteColor::EightBit(3))));
+    }
+
+    #[test]
+    fn queued_alerts_show_a_badge_and_result_counts() {
+        let mut state = two_tabs();
+        let queue = state.tab_alerts.entry(1).or_default();
+        for success in [true, false, false] {
+            queue.push(TabAlert::new(AlertType::CommandResult(CommandResult::exited(if success { 0 } else { 1 })), AlertPolicy::command_result()));
+        }
+        let frame = draw(&mut state, 10, 20);
+        assert_eq!(frame.text_lines()[6..8], [" ✗ 🚀 server (3)", "    ✓1 ✗2 · ✗ 1"]);
+    }
+
+    #[test]
+    fn collapsed_draws_emoji_only() {
+        let mut state = two_tabs();
+        state.collapsed = true;
+        let frame = draw(&mut state, 10, 6);
+        assert_eq!(frame.text(3).trim(), "🌟");
+        assert_eq!(frame.text(6).trim(), "🚀");
+    }
+
+    #[test]
+    fn compact_layout_uses_one_row_per_tab() {
+        let mut state = two_tabs();
+        state.config.tab_height = 1;
+        let frame = draw(&mut state, 6, 20);
+        assert_eq!(frame.text_lines()[2..4], [" 🌟 editor", " 🚀 server"]);
+    }
+
+    #[test]
+    fn overflowing_tabs_show_more_indicator() {
+        let names: Vec<String> = (0..10).map(|i| format!("t{}", i)).collect();
+        let mut state = state_with_tabs(&names);
+        state.config.tab_height = 1;
+        let frame = draw(&mut state, 8, 20);
+        assert_eq!(frame.text_lines()[2..8], ["", " 🌟 t0", " 🚀 t1", " 🎨 t2", " 🌈 t3", " ▼ 6 more"]);
+    }
+}
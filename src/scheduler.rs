@@ -0,0 +1,124 @@
+// One timer for everything that animates. zellij can't cancel a timeout, so
+// the scheduler remembers which ones are still pending and only asks for a new
+// one when none of them fires early enough for the next deadline.
+
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// Timer events don't arrive exactly on time, treat anything this close to a
+// deadline as reached
+pub const EPSILON: f64 = 0.001;
+
+// Seconds since the plugin loaded, tests drive the clock by hand
+#[derive(Debug)]
+pub enum Clock {
+    System(Instant),
+    #[cfg(test)]
+    Manual(f64),
+}
+
+impl Clock {
+    pub fn now(&self) -> f64 {
+        match self {
+            Clock::System(start) => start.elapsed().as_secs_f64(),
+            #[cfg(test)]
+            Clock::Manual(now) => *now,
+        }
+    }
+
+    // Wall clock time for timestamps, the manual clock starts at the epoch
+    pub fn unix_time(&self) -> u64 {
+        match self {
+            Clock::System(_) => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            #[cfg(test)]
+            Clock::Manual(now) => *now as u64,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Scheduler {
+    clock: Clock,
+    armed: Vec<f64>, // deadlines of the timeouts zellij will still deliver
+}
+
+impl Scheduler {
+    pub fn new(clock: Clock) -> Self {
+        Self {
+            clock,
+            armed: Vec::new(),
+        }
+    }
+
+    pub fn now(&self) -> f64 {
+        self.clock.now()
+    }
+
+    pub fn unix_time(&self) -> u64 {
+        self.clock.unix_time()
+    }
+
+    #[cfg(test)]
+    pub fn advance(&mut self, secs: f64) {
+        if let Clock::Manual(now) = &mut self.clock {
+            *now += secs;
+        }
+    }
+
+    // A timer event arrived, forget every timeout that is due by now
+    pub fn fired(&mut self) {
+        let now = self.now();
+        self.armed.retain(|deadline| *deadline > now + EPSILON);
+    }
+
+    // Something needs a wakeup in `delay` seconds. Returns the timeout to
+    // arm, or None when a pending one already fires by then. Timeouts past
+    // their deadline no longer count, their timer event may never come.
+    pub fn wake_in(&mut self, delay: f64) -> Option<f64> {
+        let now = self.now();
+        self.armed.retain(|armed| *armed > now);
+        let deadline = now + delay;
+        if self.armed.iter().any(|armed| *armed <= deadline + EPSILON) {
+            return None;
+        }
+        self.armed.push(deadline);
+        Some(delay)
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new(Clock::System(Instant::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_timeout_covers_later_deadlines() {
+        let mut scheduler = Scheduler::new(Clock::Manual(0.0));
+        assert_eq!(scheduler.wake_in(0.2), Some(0.2));
+        assert_eq!(scheduler.wake_in(1.0), None);
+
+        scheduler.advance(0.2);
+        scheduler.fired();
+        assert_eq!(scheduler.wake_in(1.0), Some(1.0));
+    }
+
+    #[test]
+    fn earlier_deadline_arms_another_timeout() {
+        let mut scheduler = Scheduler::new(Clock::Manual(0.0));
+        assert_eq!(scheduler.wake_in(1.0), Some(1.0));
+        scheduler.advance(0.5);
+        assert_eq!(scheduler.wake_in(0.2), Some(0.2));
+
+        // The first timeout is still pending after the early one fired
+        scheduler.advance(0.2);
+        scheduler.fired();
+        assert_eq!(scheduler.wake_in(0.5), None);
+    }
+}
@@ -0,0 +1,649 @@
+// Host-side simulator: replays scripted zellij events and pipe messages
+// against State and collects the host commands it queues, so whole scenarios
+// can be checked without running zellij.
+
+use std::collections::{BTreeMap, HashMap};
+
+use zellij_tile::prelude::*;
+
+use crate::alert::{AlertQueue, TabAlert};
+use crate::host::HostCommand;
+use crate::render::Frame;
+use crate::scheduler::{Clock, Scheduler};
+use crate::sync::{SyncState, SyncedTab};
+use crate::State;
+
+pub struct Simulator {
+    pub state: State,
+}
+
+impl Simulator {
+    pub fn new() -> Self {
+        Self::with_config(&[])
+    }
+
+    // Load the plugin with the given config and drop the startup commands
+    pub fn with_config(config: &[(&str, &str)]) -> Self {
+        let mut state = State {
+            scheduler: Scheduler::new(Clock::Manual(0.0)),
+            ..State::default()
+        };
+        state.mode_info.style.colors.ribbon_unselected.base = PaletteColor::EightBit(7);
+        state.mode_info.style.colors.ribbon_unselected.background = PaletteColor::EightBit(0);
+        let configuration = config
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        state.handle_load(configuration);
+        let mut sim = Self { state };
+        sim.take_commands();
+        sim
+    }
+
+    pub fn event(&mut self, event: Event) -> bool {
+        self.state.handle_event(event)
+    }
+
+    pub fn pipe(&mut self, pipe_message: PipeMessage) -> bool {
+        self.state.handle_pipe(pipe_message)
+    }
+
+    // Tabs named after `names`, the one at `active` (0-based) is focused.
+    // Each tab holds a single terminal pane with id 10 * position.
+    pub fn tabs(&mut self, names: &[&str], active: usize) -> bool {
+        let panes = (0..names.len()).map(|position| PaneInfo {
+            is_selectable: true,
+            ..pane(position as u32 * 10)
+        });
+        let tabs_changed = self.event(Event::TabUpdate(tab_infos(names, active)));
+        let panes_changed = self.event(Event::PaneUpdate(one_pane_per_tab(panes)));
+        tabs_changed || panes_changed
+    }
+
+    // Let `secs` pass without anything happening
+    pub fn wait(&mut self, secs: f64) {
+        self.state.scheduler.advance(secs);
+    }
+
+    // A timer armed `elapsed` seconds ago fires now
+    pub fn timer(&mut self, elapsed: f64) -> bool {
+        self.wait(elapsed);
+        self.event(Event::Timer(elapsed))
+    }
+
+    pub fn click(&mut self, row: usize) -> bool {
+        self.event(Event::Mouse(Mouse::LeftClick(row as isize, 0)))
+    }
+
+    pub fn cli_pipe(&mut self, name: &str, args: &[(&str, &str)]) -> bool {
+        self.pipe(PipeMessage {
+            source: PipeSource::Cli("sim".to_string()),
+            name: name.to_string(),
+            payload: None,
+            args: args
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<BTreeMap<_, _>>(),
+            is_private: false,
+        })
+    }
+
+    pub fn keybind(&mut self, name: &str) -> bool {
+        self.pipe(PipeMessage {
+            source: PipeSource::Keybind,
+            name: name.to_string(),
+            payload: None,
+            args: BTreeMap::new(),
+            is_private: false,
+        })
+    }
+
+    pub fn plugin_pipe(&mut self, name: &str, payload: &str) -> bool {
+        self.pipe(PipeMessage {
+            source: PipeSource::Plugin(1),
+            name: name.to_string(),
+            payload: Some(payload.to_string()),
+            args: BTreeMap::new(),
+            is_private: true,
+        })
+    }
+
+    pub fn render(&mut self, rows: usize, cols: usize) -> Frame {
+        self.state.rows = rows;
+        self.state.cols = cols;
+        self.state.draw()
+    }
+
+    pub fn take_commands(&mut self) -> Vec<HostCommand> {
+        std::mem::take(&mut self.state.commands)
+    }
+}
+
+// Answer to a catch-up request with a single alert on the tab at `position`,
+// as another sidebar instance would send it
+pub fn alert_payload(version: u64, position: usize, name: &str, alert: &TabAlert) -> String {
+    let mut alerts = AlertQueue::default();
+    alerts.push(alert.clone());
+    let state = SyncState {
+        version,
+        tabs: Some(vec![SyncedTab {
+            position,
+            name: name.to_string(),
+            alerts,
+            meta: Default::default(),
+            generated_name: None,
+        }]),
+        collapsed: false,
+        scroll_offset: 0,
+    };
+    serde_json::to_string(&state).unwrap()
+}
+
+// Fixtures for the zellij events the tests of every module build
+
+// Tabs named after `names`, the one at `active` (0-based) is focused
+pub fn tab_infos(names: &[impl AsRef<str>], active: usize) -> Vec<TabInfo> {
+    names
+        .iter()
+        .enumerate()
+        .map(|(position, name)| TabInfo {
+            position,
+            name: name.as_ref().to_string(),
+            active: position == active,
+            ..Default::default()
+        })
+        .collect()
+}
+
+// A terminal pane, hidden from the pane tree unless made selectable
+pub fn pane(id: u32) -> PaneInfo {
+    PaneInfo {
+        id,
+        ..Default::default()
+    }
+}
+
+// The given panes, all on the tab at `position`
+pub fn panes_on_tab(position: usize, panes: impl IntoIterator<Item = PaneInfo>) -> PaneManifest {
+    PaneManifest { panes: HashMap::from([(position, panes.into_iter().collect())]) }
+}
+
+// One pane per tab, in tab order
+pub fn one_pane_per_tab(panes: impl IntoIterator<Item = PaneInfo>) -> PaneManifest {
+    let panes = panes.into_iter().enumerate().map(|(position, pane)| (position, vec![pane]));
+    PaneManifest { panes: panes.collect() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::alert::{AlertPolicy, AlertType, CommandResult};
+
+    const NOTIFY: &str = "zj-status-sidebar:cli:notify";
+    const TAB_ALERT: &str = "zj-status-sidebar:cli:tab_alert";
+    const STATE: &str = "zj-status-sidebar:plugin:tab_alert:broadcast";
+    const REQUEST: &str = "zj-status-sidebar:plugin:sync_request";
+    const SET_TAB_META: &str = "zj-status-sidebar:cli:set_tab_meta";
+
+    #[test]
+    fn load_requests_permissions_and_events() {
+        let mut state = State::default();
+        state.handle_load(BTreeMap::new());
+        assert!(matches!(state.commands[0], HostCommand::RequestPermission(_)));
+        assert!(matches!(state.commands[1], HostCommand::Subscribe(_)));
+        assert_eq!(state.commands[2], HostCommand::SetSelectable(true));
+    }
+
+    #[test]
+    fn notify_arms_flash_timer_and_marks_tab() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server"], 0);
+        assert!(sim.cli_pipe(NOTIFY, &[("tab", "2")]));
+        assert_eq!(sim.take_commands(), [HostCommand::SetTimeout(0.2)]);
+        assert_eq!(sim.render(10, 20).text_lines()[6], " 🔴 🐉 server");
+
+        // Only the first alert starts the timer
+        sim.cli_pipe(NOTIFY, &[("tab_name", "server")]);
+        assert!(sim.take_commands().is_empty());
+    }
+
+    #[test]
+    fn notify_skips_the_active_tab() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server"], 0);
+        assert!(!sim.cli_pipe(NOTIFY, &[("tab", "1")]));
+        assert!(sim.take_commands().is_empty());
+    }
+
+    #[test]
+    fn notify_reaches_every_matching_tab_but_the_active_one() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["web-api", "web-ui", "db"], 0);
+        assert!(sim.cli_pipe(NOTIFY, &[("tab_glob", "web-*")]));
+        let alerted: Vec<_> = sim.state.tab_alerts.keys().collect();
+        assert_eq!(alerted, [&1]);
+    }
+
+    #[test]
+    fn command_is_shown_with_the_exit_code() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server"], 0);
+        assert!(sim.cli_pipe(TAB_ALERT, &[("pane_id", "10"), ("exit_code", "1"), ("command", "cargo test")]));
+        let alert = sim.state.tab_alerts[&1].latest().unwrap();
+        let AlertType::CommandResult(result) = &alert.alert_type else {
+            panic!("expected a command result");
+        };
+        assert_eq!(result.details(), "✗ 1 · cargo test");
+    }
+
+    #[test]
+    fn lost_timer_events_dont_stop_the_alerts() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server", "db"], 0);
+        sim.cli_pipe(NOTIFY, &[("tab", "2")]);
+        assert_eq!(sim.take_commands(), [HostCommand::SetTimeout(0.2)]);
+
+        // The timeout never fires, the next alert arms a new one
+        sim.wait(1.0);
+        sim.cli_pipe(NOTIFY, &[("tab", "3")]);
+        assert!(matches!(sim.take_commands()[..], [HostCommand::SetTimeout(_)]));
+    }
+
+    #[test]
+    fn command_result_alert_blinks_until_focused() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server"], 0);
+        sim.cli_pipe(TAB_ALERT, &[("pane_id", "10"), ("exit_code", "1")]);
+        assert_eq!(sim.take_commands(), [HostCommand::SetTimeout(1.0)]);
+
+        assert!(sim.timer(1.0));
+        assert_eq!(sim.take_commands(), [HostCommand::SetTimeout(1.0)]);
+
+        // Focusing the tab clears the alert and stops the timer
+        sim.tabs(&["editor", "server"], 1);
+        assert!(sim.state.tab_alerts.is_empty());
+        assert!(!sim.timer(1.0));
+        assert!(sim.take_commands().is_empty());
+    }
+
+    #[test]
+    fn mixed_alerts_share_one_timer_at_their_own_speed() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "build", "chat"], 0);
+        sim.cli_pipe(TAB_ALERT, &[("pane_id", "10"), ("exit_code", "0")]);
+        assert_eq!(sim.take_commands(), [HostCommand::SetTimeout(1.0)]);
+
+        // The notification needs a faster timer than the pending one
+        sim.wait(0.5);
+        sim.cli_pipe(NOTIFY, &[("tab", "3")]);
+        assert_eq!(sim.take_commands(), [HostCommand::SetTimeout(0.2)]);
+        sim.timer(0.2);
+        assert_eq!(sim.take_commands(), [HostCommand::SetTimeout(0.2)]);
+
+        // The command alert's own timer is due first, no extra timeout
+        sim.timer(0.2);
+        assert!(sim.take_commands().is_empty());
+        assert!(sim.state.tab_alerts[&1].latest().unwrap().is_lit());
+
+        // and it still blinks once a second
+        sim.timer(0.1);
+        assert!(!sim.state.tab_alerts[&1].latest().unwrap().is_lit());
+        assert!(matches!(sim.take_commands()[..], [HostCommand::SetTimeout(_)]));
+    }
+
+    #[test]
+    fn pipe_args_override_the_configured_policy() {
+        let mut sim = Simulator::with_config(&[("notification_ttl", "10")]);
+        sim.tabs(&["editor", "server"], 0);
+        sim.cli_pipe(NOTIFY, &[("tab", "2"), ("blink_count", "0"), ("ttl", "3")]);
+        // Nothing to blink, the timer only waits for the alert to expire
+        assert_eq!(sim.take_commands(), [HostCommand::SetTimeout(3.0)]);
+        assert!(sim.timer(3.0));
+        assert!(sim.state.tab_alerts.is_empty());
+        assert!(sim.take_commands().is_empty());
+    }
+
+    #[test]
+    fn history_keeps_alerts_after_they_clear() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "Tab #2", "chat"], 0);
+        sim.cli_pipe(SET_TAB_META, &[("tab", "3"), ("label", "pr-42")]);
+        sim.cli_pipe(TAB_ALERT, &[("pane_id", "10"), ("exit_code", "101")]);
+        sim.wait(120.0);
+        sim.cli_pipe(NOTIFY, &[("tab", "3"), ("message", "review ready")]);
+        sim.tabs(&["editor", "Tab #2", "chat"], 1);
+        assert!(!sim.state.tab_alerts.contains_key(&1));
+
+        sim.keybind("toggle_history");
+        assert_eq!(
+            sim.render(10, 40).text_lines()[2..5],
+            [" History · 1 unread", " 🔴 now 💎 pr-42 · review ready", " ✗ 2m 🐉 zealous cloud · exit 101"]
+        );
+
+        sim.take_commands();
+        sim.cli_pipe("zj-status-sidebar:cli:history", &[]);
+        let [HostCommand::CliPipeOutput(pipe_id, json)] = &sim.take_commands()[..] else {
+            panic!("expected a single reply");
+        };
+        assert_eq!(pipe_id, "sim");
+        assert!(json.starts_with(r#"{"unread":1,"entries":[{"at":120,"tab":3,"tab_name":"💎 pr-42""#));
+    }
+
+    #[test]
+    fn history_toggle_is_ignored_while_collapsed() {
+        let mut sim = Simulator::with_config(&[("collapsed", "true")]);
+        sim.tabs(&["editor", "build"], 0);
+        assert!(!sim.keybind("toggle_history"));
+        sim.keybind("toggle_collapse");
+        assert!(!sim.state.showing_history());
+
+        assert!(sim.keybind("toggle_history"));
+        assert!(sim.state.showing_history());
+    }
+
+    #[test]
+    fn one_sidebar_answers_queries_even_off_the_active_tab() {
+        let sidebar = |id: u32| PaneInfo {
+            is_plugin: true,
+            plugin_url: Some("file:zj-status-sidebar.wasm".to_string()),
+            ..pane(id)
+        };
+        let answers = |plugin_id: u32, panes: &PaneManifest| {
+            let mut sim = Simulator::new();
+            sim.state.plugin_id = Some(plugin_id);
+            sim.tabs(&["editor", "build", "chat"], 2);
+            sim.event(Event::PaneUpdate(panes.clone()));
+            sim.take_commands();
+            sim.cli_pipe("zj-status-sidebar:cli:history", &[]);
+            !sim.take_commands().is_empty()
+        };
+
+        // The sidebar on the active tab answers
+        let panes = one_pane_per_tab([sidebar(1), sidebar(2), sidebar(3)]);
+        assert_eq!([1, 2, 3].map(|id| answers(id, &panes)), [false, false, true]);
+
+        // Without one there, the lowest plugin id does
+        let panes = one_pane_per_tab([sidebar(2), sidebar(1), pane(30)]);
+        assert_eq!([1, 2].map(|id| answers(id, &panes)), [true, false]);
+    }
+
+    #[test]
+    fn pane_alert_on_the_active_tab_waits_for_pane_focus() {
+        let mut sim = Simulator::with_config(&[("pane_alerts", "true")]);
+        sim.tabs(&["editor", "server"], 0);
+        let focus = |focused: u32| {
+            let panes = (0..2).map(|id| PaneInfo {
+                is_selectable: true,
+                is_focused: id == focused,
+                ..pane(id)
+            });
+            Event::PaneUpdate(panes_on_tab(0, panes))
+        };
+        sim.event(focus(0));
+
+        // The focused pane needs no alert, the other one does
+        assert!(!sim.cli_pipe(TAB_ALERT, &[("pane_id", "0"), ("exit_code", "1")]));
+        assert!(sim.cli_pipe(TAB_ALERT, &[("pane_id", "1"), ("exit_code", "1")]));
+        assert_eq!(sim.state.tab_alerts[&0].latest().unwrap().pane_id, Some(1));
+
+        assert!(sim.event(focus(1)));
+        assert!(sim.state.tab_alerts.is_empty());
+    }
+
+    #[test]
+    fn pane_alerts_are_off_by_default() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server"], 0);
+        assert!(!sim.cli_pipe(NOTIFY, &[("pane_id", "0")]));
+    }
+
+    #[test]
+    fn finished_command_panes_alert_without_the_wrapper() {
+        let mut sim = Simulator::with_config(&[("auto_alert_commands", "cargo")]);
+        sim.tabs(&["editor", "build"], 0);
+        let build = |exited: bool| {
+            let pane = PaneInfo {
+                terminal_command: Some("cargo build".to_string()),
+                exited,
+                exit_status: exited.then_some(101),
+                ..pane(10)
+            };
+            Event::PaneUpdate(panes_on_tab(1, [pane]))
+        };
+        assert!(!sim.event(build(false)));
+        sim.wait(90.0);
+        assert!(sim.event(build(true)));
+        assert_eq!(sim.take_commands(), [HostCommand::SetTimeout(1.0)]);
+        assert_eq!(sim.render(10, 40).text_lines()[7], "    ✗ 101 · cargo build · 1m30s");
+    }
+
+    #[test]
+    fn title_matching_a_rule_notifies_its_tab() {
+        let mut sim = Simulator::with_config(&[("title_alert_error", "❌")]);
+        sim.tabs(&["editor", "build"], 0);
+        let title = |title: &str| {
+            let pane = PaneInfo {
+                title: title.to_string(),
+                ..pane(10)
+            };
+            Event::PaneUpdate(panes_on_tab(1, [pane]))
+        };
+        assert!(!sim.event(title("make")));
+        assert!(sim.event(title("make ❌")));
+        // Staying on the same title doesn't notify again
+        assert!(!sim.event(title("make ❌")));
+        assert_eq!(sim.state.tab_alerts[&1].len(), 1);
+        assert_eq!(sim.render(10, 30).text_lines()[6..8], [" 🔴 🐉 build", "    make ❌"]);
+    }
+
+    #[test]
+    fn clicking_a_tab_switches_to_it() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server"], 0);
+        sim.render(10, 20);
+        sim.click(6);
+        assert_eq!(sim.take_commands(), [HostCommand::SwitchTabTo(2)]);
+    }
+
+    #[test]
+    fn first_catch_up_answer_replaces_the_alerts() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server", "db"], 0);
+        sim.cli_pipe(NOTIFY, &[("tab", "3")]);
+        sim.take_commands();
+
+        let alert = TabAlert::new(AlertType::CommandResult(CommandResult::exited(0)), AlertPolicy::command_result());
+        assert!(sim.plugin_pipe(STATE, &alert_payload(1, 1, "server", &alert)));
+        assert_eq!(sim.state.tab_alerts.len(), 1);
+        assert_eq!(sim.state.tab_alerts[&1].latest(), Some(&alert));
+        // The notification's pending timer fires before the alert blinks
+        assert!(sim.take_commands().is_empty());
+
+        // Later answers don't undo what this sidebar applied since
+        let failed = TabAlert::new(AlertType::CommandResult(CommandResult::exited(1)), AlertPolicy::command_result());
+        assert!(!sim.plugin_pipe(STATE, &alert_payload(2, 1, "server", &failed)));
+        assert_eq!(sim.state.tab_alerts[&1].latest(), Some(&alert));
+    }
+
+    #[test]
+    fn catch_up_answers_leave_out_tabs_seen_differently() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server"], 0);
+        let alert = TabAlert::new(AlertType::Notification, AlertPolicy::notification());
+        assert!(!sim.plugin_pipe(STATE, &alert_payload(1, 1, "web", &alert)));
+        assert!(sim.state.tab_alerts.is_empty());
+    }
+
+    #[test]
+    fn view_changes_elsewhere_keep_the_alerts_raised_here() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server", "db"], 0);
+        sim.cli_pipe(NOTIFY, &[("tab", "3")]);
+
+        // The other sidebar collapses before the notification reached it
+        let mut other = Simulator::new();
+        other.tabs(&["editor", "server", "db"], 0);
+        other.click(0);
+        let [HostCommand::MessageToPlugin(None, _, payload)] = &other.take_commands()[..] else {
+            panic!("expected a broadcast");
+        };
+        assert!(sim.plugin_pipe(STATE, payload));
+        assert!(sim.state.collapsed);
+        assert!(sim.state.tab_alerts.contains_key(&2));
+    }
+
+    #[test]
+    fn every_sidebar_toggles_on_the_keybind_by_itself() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server"], 0);
+        assert!(sim.keybind("toggle_collapse"));
+        assert!(sim.state.collapsed);
+        assert!(sim.take_commands().is_empty());
+    }
+
+    #[test]
+    fn local_changes_are_broadcast_with_a_new_version() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server"], 0);
+        sim.click(0);
+        let [HostCommand::MessageToPlugin(None, name, payload)] = &sim.take_commands()[..] else {
+            panic!("expected a broadcast");
+        };
+        assert_eq!(name, STATE);
+        let state: SyncState = serde_json::from_str(payload).unwrap();
+        assert_eq!((state.version, state.collapsed), (1, true));
+
+        // A state the other sidebar wrote before seeing ours loses
+        assert!(!sim.plugin_pipe(STATE, &serde_json::to_string(&SyncState { collapsed: false, version: 0, ..state }).unwrap()));
+        assert!(sim.state.collapsed);
+    }
+
+    #[test]
+    fn new_sidebars_catch_up_from_the_others() {
+        let mut sim = Simulator::new();
+        sim.event(Event::PermissionRequestResult(PermissionStatus::Granted));
+        let commands = sim.take_commands();
+        assert_eq!(commands[1], HostCommand::MessageToPlugin(None, REQUEST.to_string(), String::new()));
+
+        // Answers arriving before the first tab update wait for it
+        let alert = TabAlert::new(AlertType::Notification, AlertPolicy::notification());
+        sim.plugin_pipe(STATE, &alert_payload(3, 1, "server", &alert));
+        sim.tabs(&["editor", "server"], 0);
+        assert!(sim.state.tab_alerts.contains_key(&1));
+
+        // and this one answers the next newcomer
+        sim.take_commands();
+        sim.plugin_pipe(REQUEST, "");
+        assert!(matches!(&sim.take_commands()[..], [HostCommand::MessageToPlugin(Some(1), name, _)] if name == STATE));
+    }
+
+    #[test]
+    fn catch_up_answers_carry_alerts_as_of_now() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server"], 0);
+        sim.cli_pipe(NOTIFY, &[("tab", "2"), ("ttl", "1")]);
+        sim.take_commands();
+
+        // The alert expired without a timer getting to it
+        sim.wait(2.0);
+        sim.plugin_pipe(REQUEST, "");
+        let [HostCommand::MessageToPlugin(Some(_), _, payload)] = &sim.take_commands()[..] else {
+            panic!("expected an answer");
+        };
+        let state: SyncState = serde_json::from_str(payload).unwrap();
+        assert!(state.tabs.unwrap().iter().all(|t| t.alerts.is_empty()));
+    }
+
+    #[test]
+    fn tab_meta_reaches_new_sidebars() {
+        let mut sim = Simulator::new();
+        sim.tabs(&["editor", "server"], 0);
+        sim.cli_pipe(SET_TAB_META, &[("tab", "2"), ("emoji", "🐛")]);
+        sim.take_commands();
+        sim.plugin_pipe(REQUEST, "");
+        let [HostCommand::MessageToPlugin(Some(_), _, payload)] = &sim.take_commands()[..] else {
+            panic!("expected an answer");
+        };
+
+        let mut newcomer = Simulator::new();
+        newcomer.tabs(&["editor", "server"], 0);
+        assert!(newcomer.plugin_pipe(STATE, payload));
+        assert_eq!(newcomer.state.tab_meta[&1].emoji.as_deref(), Some("🐛"));
+    }
+
+    #[test]
+    fn panes_arriving_after_their_tabs_name_them() {
+        let mut sim = Simulator::new();
+        sim.event(Event::TabUpdate(tab_infos(&["Tab #1", "Tab #2"], 0)));
+        assert_eq!(sim.render(10, 20).text(3).trim_end(), " Tab #1");
+
+        let panes = one_pane_per_tab([pane(0), pane(10)]);
+        assert!(sim.event(Event::PaneUpdate(panes.clone())));
+        assert_eq!(sim.render(10, 20).text(3).trim_end(), " 🌟 happy fox");
+
+        // Nothing new to name the second time
+        assert!(!sim.event(Event::PaneUpdate(panes)));
+    }
+
+    #[test]
+    fn tabs_out_of_view_are_named_for_new_sidebars() {
+        let names = ["Tab #1", "Tab #2", "Tab #3", "Tab #4", "Tab #5", "Tab #6"];
+        let mut sim = Simulator::new();
+        sim.render(8, 20);
+        sim.tabs(&names, 5);
+        sim.plugin_pipe(REQUEST, "");
+        let [HostCommand::MessageToPlugin(Some(_), _, payload)] = &sim.take_commands()[..] else {
+            panic!("expected an answer");
+        };
+        let state: SyncState = serde_json::from_str(payload).unwrap();
+        // Scrolled to the active tab and every tab named before any of
+        // them was drawn
+        assert_eq!(state.scroll_offset, 5);
+        let tabs = state.tabs.unwrap();
+        assert_eq!(tabs.len(), names.len());
+        assert!(tabs.iter().all(|t| t.generated_name.is_some()));
+        assert_eq!(sim.render(8, 20).text(2).trim_end(), " ▲ 5 more");
+    }
+
+    #[test]
+    fn generated_names_agree_between_sidebars() {
+        let tab_update = |names: &[&str], pane_ids: &[u32]| {
+            let panes = pane_ids.iter().map(|&id| pane(id));
+            (Event::TabUpdate(tab_infos(names, 0)), Event::PaneUpdate(one_pane_per_tab(panes)))
+        };
+        let generated = |sim: &Simulator, position: usize| {
+            let tab_id = sim.state.tab_ids.id_at(position).unwrap();
+            sim.state.name_cache.get(tab_id).map(str::to_string)
+        };
+
+        // This sidebar saw a tab that is gone, so its tab ids are shifted
+        let mut sim = Simulator::new();
+        sim.tabs(&["logs", "Tab #2", "Tab #3"], 0);
+        let (tabs, panes) = tab_update(&["Tab #2", "Tab #3"], &[10, 20]);
+        sim.event(tabs.clone());
+        sim.event(panes.clone());
+        sim.render(10, 40);
+
+        let mut other = Simulator::new();
+        other.event(tabs);
+        other.event(panes);
+        other.render(10, 40);
+        assert_ne!(sim.state.tab_ids.id_at(0), other.state.tab_ids.id_at(0));
+        assert_eq!(generated(&sim, 0), generated(&other, 0));
+
+        // A late sidebar never sees the pane the first tab was named after
+        let mut late = Simulator::new();
+        let (tabs, panes) = tab_update(&["Tab #2", "Tab #3"], &[11, 20]);
+        late.event(tabs);
+        late.event(panes);
+        late.render(10, 40);
+        assert_ne!(generated(&late, 0), generated(&sim, 0));
+        sim.plugin_pipe(REQUEST, "");
+        let [HostCommand::MessageToPlugin(Some(_), _, payload)] = &sim.take_commands()[..] else {
+            panic!("expected an answer");
+        };
+        assert!(late.plugin_pipe(STATE, payload));
+        assert_eq!(generated(&late, 0), generated(&sim, 0));
+    }
+}
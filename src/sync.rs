@@ -0,0 +1,95 @@
+// Keeps the sidebar instances of a session showing the same thing. Every tab
+// runs its own instance. CLI pipes, keybinds, tab and pane updates reach all
+// of them and are applied by each on its own, including clearing the alerts
+// of a tab that gets focused. Clicks and scrolling only happen in one
+// instance, so that one broadcasts the view state (collapsed and scroll
+// offset) they change.
+//
+// View states are versioned with a counter bumped on every local change. An
+// instance adopts one only when it is newer than the last one it wrote or
+// adopted, ties go to the higher plugin id, so concurrent changes settle on the
+// same winner everywhere.
+//
+// An instance that starts late missed the alerts, meta and generated names
+// the others already have. It asks for them once it has permissions and takes
+// the tabs of the first answer, after that it keeps up on its own like the
+// rest. Tab ids are assigned by each instance in the order it saw the tabs, so
+// on the wire tabs go by position and name. Tabs without alerts, meta or a
+// generated name are left out.
+
+use serde::{Deserialize, Serialize};
+
+use crate::alert::AlertQueue;
+use crate::tab::TabMeta;
+
+pub const STATE_MESSAGE: &str = "zj-status-sidebar:plugin:tab_alert:broadcast";
+pub const REQUEST_MESSAGE: &str = "zj-status-sidebar:plugin:sync_request";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SyncedTab {
+    pub position: usize,
+    pub name: String,
+    pub alerts: AlertQueue,
+    #[serde(default, skip_serializing_if = "TabMeta::is_empty")]
+    pub meta: TabMeta,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SyncState {
+    pub version: u64,
+    // Only in answers to a catch-up request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tabs: Option<Vec<SyncedTab>>,
+    pub collapsed: bool,
+    pub scroll_offset: usize,
+}
+
+// Version of the state an instance shows and which plugin wrote it
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct SyncClock {
+    version: u64,
+    writer: u32,
+}
+
+impl SyncClock {
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    // A local change, returns the version to publish it as
+    pub fn bump(&mut self, own_id: u32) -> u64 {
+        self.version += 1;
+        self.writer = own_id;
+        self.version
+    }
+
+    // Take a received state's version if it's newer than ours, returns
+    // whether it should be adopted
+    pub fn observe(&mut self, version: u64, writer: u32) -> bool {
+        let received = SyncClock { version, writer };
+        if received > *self {
+            *self = received;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newer_versions_win_and_ties_go_to_the_higher_plugin() {
+        let mut clock = SyncClock::default();
+        assert_eq!(clock.bump(2), 1);
+        assert!(!clock.observe(1, 1));
+        assert!(clock.observe(1, 3));
+        assert!(!clock.observe(1, 3));
+        assert!(clock.observe(2, 1));
+        assert_eq!(clock.bump(2), 3);
+    }
+}
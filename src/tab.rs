@@ -1,2 +1,100 @@
-// Tab module - currently unused but kept for future extension
-// The code that was here used the old Palette API which is incompatible with zellij-tile 0.42.2
\ No newline at end of file
+// Per-tab overrides, status glyphs and the pane tree shown under each tab entry
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use zellij_tile::prelude::*;
+
+use crate::config::{parse_color, Config};
+
+// User-assigned overrides set through the set_tab_meta pipe
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TabMeta {
+    pub emoji: Option<String>,
+    pub label: Option<String>,
+    pub color: Option<PaletteColor>,
+}
+
+impl TabMeta {
+    // Apply the `emoji`, `label` and `color` pipe args, an empty value clears
+    // that override. Nothing is applied if any arg is invalid.
+    pub fn apply_args(&mut self, args: &BTreeMap<String, String>) -> Result<(), String> {
+        let color = match args.get("color").map(String::as_str) {
+            Some("") => Some(None),
+            Some(value) => Some(Some(parse_color(value)?)),
+            None => None,
+        };
+        let non_empty = |value: &String| Some(value.clone()).filter(|v| !v.is_empty());
+        if let Some(emoji) = args.get("emoji") {
+            self.emoji = non_empty(emoji);
+        }
+        if let Some(label) = args.get("label") {
+            self.label = non_empty(label);
+        }
+        if let Some(color) = color {
+            self.color = color;
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.emoji.is_none() && self.label.is_none() && self.color.is_none()
+    }
+}
+
+// Status glyphs appended to a tab label, e.g. " ⛶ ⇄ ◫ ▣3"
+pub fn status_glyphs(t: &TabInfo, config: &Config) -> String {
+    let mut glyphs = String::new();
+    if config.show_fullscreen && t.is_fullscreen_active {
+        glyphs.push_str(" ⛶");
+    }
+    if config.show_sync_panes && t.is_sync_panes_active {
+        glyphs.push_str(" ⇄");
+    }
+    if config.show_floating_panes && t.are_floating_panes_visible {
+        glyphs.push_str(" ◫");
+    }
+    if config.show_pane_count {
+        let count = t.selectable_tiled_panes_count + t.selectable_floating_panes_count;
+        glyphs.push_str(&format!(" ▣{}", count));
+    }
+    glyphs
+}
+
+// Panes worth listing for a tab: hidden (suppressed) panes and UI panes such
+// as this sidebar itself are left out
+pub fn listed_panes(panes: Option<&Vec<PaneInfo>>) -> Vec<&PaneInfo> {
+    panes
+        .map(|panes| {
+            panes
+                .iter()
+                .filter(|p| p.is_selectable && !p.is_suppressed)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Oldest terminal pane of a tab, pane ids are shared by the whole session
+pub fn first_terminal_pane(panes: Option<&Vec<PaneInfo>>) -> Option<u32> {
+    panes?.iter().filter(|p| !p.is_plugin).map(|p| p.id).min()
+}
+
+// One tree row, e.g. "├▸cargo build ✗ 101"
+pub fn pane_label(pane: &PaneInfo, is_last: bool) -> String {
+    let branch = if is_last { "└" } else { "├" };
+    let focus = if pane.is_focused { "▸" } else { " " };
+    let name = match &pane.terminal_command {
+        Some(command) if !command.is_empty() => command.as_str(),
+        _ => pane.title.as_str(),
+    };
+    let status = if pane.exited {
+        match pane.exit_status {
+            Some(0) => String::from(" ✓"),
+            Some(code) => format!(" ✗ {}", code),
+            None => String::from(" ✗"),
+        }
+    } else {
+        String::new()
+    };
+    format!("  {}{}{}{}", branch, focus, name, status)
+}
@@ -0,0 +1,199 @@
+// Which tabs a pipe message is about. Every pipe takes the same target args:
+//
+//   pane_id=12          the tab holding terminal pane 12 ($ZELLIJ_PANE_ID)
+//   tab=2               the 2nd tab
+//   tab_name=main       tabs named exactly "main"
+//   tab_glob=web-*      tabs whose name matches a glob (`*` and `?`)
+//   tab_regex=^api      tabs whose name matches a regex
+//   running=cargo       tabs with a pane running `cargo`
+
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use zellij_tile::prelude::*;
+
+#[derive(Debug, Clone)]
+pub enum Target {
+    Pane(u32),
+    Tab(usize), // 0-based position
+    TabName(String),
+    TabPattern(Pattern),
+    Running(String), // program name
+}
+
+impl Target {
+    // The first target arg found wins, in the order listed above
+    pub fn from_args(args: &BTreeMap<String, String>) -> Result<Self, String> {
+        if let Some(value) = args.get("pane_id") {
+            return value
+                .parse()
+                .map(Target::Pane)
+                .map_err(|_| format!("pane_id: expected a pane id, got '{}'", value));
+        }
+        if let Some(value) = args.get("tab") {
+            return match value.parse::<usize>() {
+                Ok(tab) if tab > 0 => Ok(Target::Tab(tab - 1)),
+                _ => Err(format!("tab: expected a tab number from 1, got '{}'", value)),
+            };
+        }
+        if let Some(value) = args.get("tab_name") {
+            return Ok(Target::TabName(value.clone()));
+        }
+        if let Some(value) = args.get("tab_glob") {
+            return Ok(Target::TabPattern(Pattern::glob(value)));
+        }
+        if let Some(value) = args.get("tab_regex") {
+            return Pattern::regex(value)
+                .map(Target::TabPattern)
+                .map_err(|e| format!("tab_regex: {}", e));
+        }
+        if let Some(value) = args.get("running") {
+            if value.is_empty() {
+                return Err(String::from("running: expected a program name"));
+            }
+            return Ok(Target::Running(value.clone()));
+        }
+        Err(String::from(
+            "expected one of pane_id, tab, tab_name, tab_glob, tab_regex or running",
+        ))
+    }
+
+    // Positions of the matching tabs, in tab order
+    pub fn resolve(&self, tabs: &[TabInfo], panes: &PaneManifest) -> Vec<usize> {
+        let tab_panes = |position: usize| panes.panes.get(&position).into_iter().flatten();
+        (0..tabs.len())
+            .filter(|position| match self {
+                Target::Pane(id) => tab_panes(*position).any(|p| !p.is_plugin && p.id == *id),
+                Target::Tab(tab) => position == tab,
+                Target::TabName(name) => tabs[*position].name == *name,
+                Target::TabPattern(pattern) => pattern.is_match(&tabs[*position].name),
+                Target::Running(program) => tab_panes(*position).any(|p| runs_command(p, program)),
+            })
+            .collect()
+    }
+}
+
+// A glob or regex as the user wrote it, compiled once up front
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    source: String,
+    regex: Regex,
+}
+
+impl Pattern {
+    // Whole-name glob, `*` matches any run of characters and `?` a single one
+    pub fn glob(glob: &str) -> Self {
+        let mut pattern = String::from("^");
+        for c in glob.chars() {
+            match c {
+                '*' => pattern.push_str(".*"),
+                '?' => pattern.push('.'),
+                _ => pattern.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        pattern.push('$');
+        Self {
+            source: glob.to_string(),
+            regex: Regex::new(&pattern).expect("escaped glob is a valid regex"),
+        }
+    }
+
+    pub fn regex(regex: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            source: regex.to_string(),
+            regex: Regex::new(regex)?,
+        })
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        self.regex.is_match(value)
+    }
+}
+
+// Regex has no PartialEq, patterns written the same match the same
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+// Program a command line runs: its first word without the directory, e.g.
+// "cargo" for "/usr/bin/cargo watch". Empty for an empty line.
+pub fn program_name(command: &str) -> &str {
+    command
+        .split_whitespace()
+        .next()
+        .map(|word| word.rsplit('/').next().unwrap_or(word))
+        .unwrap_or_default()
+}
+
+// A command pane's own command by program name, or whatever the shell put in
+// the title while running something by its first word. Titles are left whole,
+// shells that title panes with the cwd (`~/src/cargo`) aren't running it.
+fn runs_command(pane: &PaneInfo, program: &str) -> bool {
+    !pane.is_plugin
+        && (pane.terminal_command.as_deref().map(program_name) == Some(program)
+            || pane.title.split_whitespace().next() == Some(program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::{one_pane_per_tab, pane, tab_infos};
+
+    fn args(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn resolve(pairs: &[(&str, &str)], tabs: &[TabInfo], panes: &PaneManifest) -> Vec<usize> {
+        Target::from_args(&args(pairs)).unwrap().resolve(tabs, panes)
+    }
+
+    #[test]
+    fn resolves_tabs_by_name_glob_and_regex() {
+        let tabs = tab_infos(&["web-api", "web-ui", "db"], 0);
+        let panes = PaneManifest::default();
+        assert_eq!(resolve(&[("tab", "3")], &tabs, &panes), [2]);
+        assert_eq!(resolve(&[("tab_name", "db")], &tabs, &panes), [2]);
+        assert_eq!(resolve(&[("tab_glob", "web-*")], &tabs, &panes), [0, 1]);
+        assert_eq!(resolve(&[("tab_regex", "ui$")], &tabs, &panes), [1]);
+        assert!(resolve(&[("tab", "4")], &tabs, &panes).is_empty());
+    }
+
+    #[test]
+    fn resolves_tabs_by_pane_and_command() {
+        let tabs = tab_infos(&["editor", "build"], 0);
+        let running = |id, command: Option<&str>, title: &str| PaneInfo {
+            terminal_command: command.map(String::from),
+            title: title.to_string(),
+            ..pane(id)
+        };
+        let panes = one_pane_per_tab([
+            running(1, None, "nvim src/main.rs"),
+            running(2, Some("/usr/bin/cargo watch"), "cargo"),
+        ]);
+        assert_eq!(resolve(&[("pane_id", "2")], &tabs, &panes), [1]);
+        assert_eq!(resolve(&[("running", "cargo")], &tabs, &panes), [1]);
+        assert_eq!(resolve(&[("running", "nvim")], &tabs, &panes), [0]);
+    }
+
+    #[test]
+    fn cwd_titles_dont_count_as_running() {
+        let tabs = tab_infos(&["cargo", "make"], 0);
+        let titled = |id, title: &str| PaneInfo {
+            title: title.to_string(),
+            ..pane(id)
+        };
+        let panes = one_pane_per_tab([titled(1, "~/src/cargo"), titled(2, "user@host:~/proj/make")]);
+        assert!(resolve(&[("running", "cargo")], &tabs, &panes).is_empty());
+        assert!(resolve(&[("running", "make")], &tabs, &panes).is_empty());
+    }
+
+    #[test]
+    fn rejects_missing_or_invalid_targets() {
+        assert!(Target::from_args(&args(&[])).is_err());
+        assert!(Target::from_args(&args(&[("tab", "0")])).is_err());
+        assert!(Target::from_args(&args(&[("tab_regex", "(")])).is_err());
+        assert!(Target::from_args(&args(&[("running", "")])).is_err());
+    }
+}
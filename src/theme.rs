@@ -0,0 +1,154 @@
+// Colors by what they are for rather than where zellij keeps them. A theme
+// starts from a preset, by default the zellij theme itself, and single roles
+// can be overridden from the plugin config.
+
+use zellij_tile::prelude::*;
+
+use crate::alert::Severity;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Preset {
+    Zellij, // follow the zellij theme
+    Dark,   // plain ANSI colors on black
+    Light,  // plain ANSI colors on white
+}
+
+pub fn parse_preset(value: &str) -> Result<Preset, String> {
+    match value {
+        "zellij" => Ok(Preset::Zellij),
+        "dark" => Ok(Preset::Dark),
+        "light" => Ok(Preset::Light),
+        _ => Err(format!("expected zellij, dark or light, got '{}'", value)),
+    }
+}
+
+// `*_color` config keys, each replaces one role of the preset
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ColorOverrides {
+    pub text: Option<PaletteColor>,
+    pub background: Option<PaletteColor>,
+    pub active_tab: Option<PaletteColor>,
+    pub inactive_tab: Option<PaletteColor>,
+    pub header: Option<PaletteColor>,
+    pub separator: Option<PaletteColor>,
+    pub success: Option<PaletteColor>,
+    pub failure: Option<PaletteColor>,
+    pub notification: Option<PaletteColor>,
+    pub info: Option<PaletteColor>,
+    pub warning: Option<PaletteColor>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub text: PaletteColor, // pane tree, history and hints
+    pub background: PaletteColor,
+    pub active_tab: PaletteColor, // highlight behind the active tab
+    pub inactive_tab: PaletteColor, // other tabs' labels
+    pub header: Option<PaletteColor>, // None follows the input mode
+    pub separator: PaletteColor, // line above the keybinding hints
+    pub success: PaletteColor,
+    pub failure: PaletteColor,
+    pub notification: PaletteColor, // notifications without a severity
+    pub info: PaletteColor,
+    pub warning: PaletteColor,
+}
+
+impl Theme {
+    pub fn new(preset: Preset, overrides: &ColorOverrides, colors: &Styling) -> Self {
+        let base = match preset {
+            Preset::Zellij => Self::zellij(colors),
+            Preset::Dark => Self::ansi(PaletteColor::EightBit(7), PaletteColor::EightBit(0)),
+            Preset::Light => Self::ansi(PaletteColor::EightBit(0), PaletteColor::EightBit(15)),
+        };
+        Self {
+            text: overrides.text.unwrap_or(base.text),
+            background: overrides.background.unwrap_or(base.background),
+            active_tab: overrides.active_tab.unwrap_or(base.active_tab),
+            inactive_tab: overrides.inactive_tab.unwrap_or(base.inactive_tab),
+            header: overrides.header.or(base.header),
+            separator: overrides.separator.unwrap_or(base.separator),
+            success: overrides.success.unwrap_or(base.success),
+            failure: overrides.failure.unwrap_or(base.failure),
+            notification: overrides.notification.unwrap_or(base.notification),
+            info: overrides.info.unwrap_or(base.info),
+            warning: overrides.warning.unwrap_or(base.warning),
+        }
+    }
+
+    // Notifications without a severity keep the original notification color
+    pub fn severity(&self, severity: Option<Severity>) -> PaletteColor {
+        match severity {
+            None => self.notification,
+            Some(Severity::Info) => self.info,
+            Some(Severity::Success) => self.success,
+            Some(Severity::Warning) => self.warning,
+            Some(Severity::Error) => self.failure,
+        }
+    }
+
+    // Alerts take the colors zellij itself uses for exit codes, falling back
+    // to related entries and then plain ANSI when a theme leaves those unset
+    fn zellij(colors: &Styling) -> Self {
+        let text = colors.ribbon_unselected.base;
+        let failure = first_set(&[colors.exit_code_error.base], PaletteColor::EightBit(1));
+        Self {
+            text,
+            background: colors.ribbon_unselected.background,
+            active_tab: text,
+            inactive_tab: text,
+            header: None,
+            separator: text,
+            success: first_set(
+                &[colors.exit_code_success.base, colors.frame_highlight.background],
+                PaletteColor::EightBit(2),
+            ),
+            failure,
+            notification: failure,
+            info: colors.text_unselected.emphasis_1,
+            warning: colors.text_unselected.emphasis_0,
+        }
+    }
+
+    fn ansi(text: PaletteColor, background: PaletteColor) -> Self {
+        Self {
+            text,
+            background,
+            active_tab: PaletteColor::EightBit(4),
+            inactive_tab: text,
+            header: None,
+            separator: PaletteColor::EightBit(8),
+            success: PaletteColor::EightBit(2),
+            failure: PaletteColor::EightBit(1),
+            notification: PaletteColor::EightBit(5),
+            info: PaletteColor::EightBit(6),
+            warning: PaletteColor::EightBit(3),
+        }
+    }
+}
+
+// Themes that don't define an entry leave it at the default black
+fn first_set(candidates: &[PaletteColor], fallback: PaletteColor) -> PaletteColor {
+    candidates
+        .iter()
+        .copied()
+        .find(|color| *color != PaletteColor::default())
+        .unwrap_or(fallback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_replace_single_roles_of_the_preset() {
+        let overrides = ColorOverrides {
+            failure: Some(PaletteColor::EightBit(9)),
+            header: Some(PaletteColor::EightBit(4)),
+            ..Default::default()
+        };
+        let theme = Theme::new(Preset::Dark, &overrides, &Styling::default());
+        assert_eq!(theme.failure, PaletteColor::EightBit(9));
+        assert_eq!(theme.header, Some(PaletteColor::EightBit(4)));
+        assert_eq!(theme.success, PaletteColor::EightBit(2));
+    }
+}
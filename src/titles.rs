@@ -0,0 +1,93 @@
+// Notifications from pane titles. Plenty of tools and prompt hooks announce
+// that something finished by setting the terminal title, so titles are
+// compared between pane updates and a title that changes into one matching a
+// configured rule notifies the pane's tab.
+
+use std::collections::HashMap;
+
+use zellij_tile::prelude::*;
+
+use crate::alert::Severity;
+use crate::target::Pattern;
+
+// A `title_alert` config rule, the regex is compiled when the config is parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitleRule {
+    pub severity: Option<Severity>,
+    pattern: Pattern,
+}
+
+impl TitleRule {
+    pub fn parse(severity: Option<Severity>, value: &str) -> Result<Self, String> {
+        let pattern = Pattern::regex(value).map_err(|e| format!("invalid regex: {}", e))?;
+        Ok(Self { severity, pattern })
+    }
+}
+
+// The rule a title falls under, the most severe one when several match
+pub fn matching_rule<'a>(rules: &'a [TitleRule], title: &str) -> Option<&'a TitleRule> {
+    rules
+        .iter()
+        .filter(|rule| rule.pattern.is_match(title))
+        .max_by_key(|rule| rule.severity)
+}
+
+#[derive(Debug, Default)]
+pub struct TitleWatch {
+    titles: HashMap<u32, String>, // terminal pane id -> last seen title
+}
+
+impl TitleWatch {
+    // Note the current titles, returns the (tab position, pane id, title) of
+    // panes whose title changed since the last update. Panes seen for the
+    // first time don't count as a change.
+    pub fn update(&mut self, manifest: &PaneManifest) -> Vec<(usize, u32, String)> {
+        let mut changed = Vec::new();
+        let mut titles = HashMap::new();
+        for (&position, panes) in &manifest.panes {
+            for pane in panes.iter().filter(|p| !p.is_plugin) {
+                if self.titles.get(&pane.id).is_some_and(|title| *title != pane.title) {
+                    changed.push((position, pane.id, pane.title.clone()));
+                }
+                titles.insert(pane.id, pane.title.clone());
+            }
+        }
+        self.titles = titles;
+        changed.sort_by_key(|(position, pane_id, _)| (*position, *pane_id));
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::{pane, panes_on_tab};
+
+    fn manifest(title: &str) -> PaneManifest {
+        let pane = PaneInfo {
+            title: title.to_string(),
+            ..pane(4)
+        };
+        panes_on_tab(2, [pane])
+    }
+
+    #[test]
+    fn reports_title_changes_only() {
+        let mut watch = TitleWatch::default();
+        assert!(watch.update(&manifest("build ❌")).is_empty());
+        assert!(watch.update(&manifest("build ❌")).is_empty());
+        assert_eq!(watch.update(&manifest("build done")), [(2, 4, "build done".to_string())]);
+    }
+
+    #[test]
+    fn most_severe_matching_rule_wins() {
+        let rules = [
+            TitleRule::parse(None, "done").unwrap(),
+            TitleRule::parse(Some(Severity::Error), "❌|failed").unwrap(),
+        ];
+        assert_eq!(matching_rule(&rules, "done, 2 failed").unwrap().severity, Some(Severity::Error));
+        assert_eq!(matching_rule(&rules, "done").unwrap().severity, None);
+        assert!(matching_rule(&rules, "zsh").is_none());
+        assert!(TitleRule::parse(None, "(").is_err());
+    }
+}
@@ -0,0 +1,112 @@
+// Command panes (`zellij run`, `command` panes in layouts) report when their
+// command exits and with which status, so their results can be alerted on
+// without the `zw` wrapper. Panes are compared between updates, each run is
+// reported once when it goes from running to exited.
+
+use std::collections::HashMap;
+
+use zellij_tile::prelude::*;
+
+use crate::alert::CommandResult;
+
+#[derive(Debug, Default)]
+pub struct CommandWatch {
+    // Command panes seen running, with when they started when that was seen
+    // too (panes already running on load have no start)
+    running: HashMap<u32, Option<f64>>,
+    primed: bool,
+}
+
+impl CommandWatch {
+    // Note what's running, returns the (tab position, pane id, result) of
+    // every command that finished since the last update
+    pub fn update(&mut self, manifest: &PaneManifest, now: f64) -> Vec<(usize, u32, CommandResult)> {
+        let mut finished = Vec::new();
+        let mut still_running = HashMap::new();
+        for (&position, panes) in &manifest.panes {
+            for pane in panes.iter().filter(|p| !p.is_plugin) {
+                let Some(command) = &pane.terminal_command else {
+                    continue;
+                };
+                let started = self.running.get(&pane.id).copied();
+                if !pane.exited {
+                    let started = started.unwrap_or(self.primed.then_some(now));
+                    still_running.insert(pane.id, started);
+                } else if let (Some(started), Some(status)) = (started, pane.exit_status) {
+                    finished.push((
+                        position,
+                        pane.id,
+                        CommandResult {
+                            command: Some(command.clone()),
+                            duration_ms: started.map(|started| ((now - started) * 1000.0) as u64),
+                            ..CommandResult::exited(shell_exit_code(status))
+                        },
+                    ));
+                }
+            }
+        }
+        // Closed panes and finished runs are dropped, a re-run starts over
+        self.running = still_running;
+        self.primed = true;
+        finished.sort_by_key(|(position, pane_id, _)| (*position, *pane_id));
+        finished
+    }
+}
+
+// Killed by signal N shows up as -N, report it the way shells (and so `zw`)
+// do, as 128 + N: 137 for SIGKILL, 130 for SIGINT
+fn shell_exit_code(status: i32) -> u32 {
+    if status < 0 {
+        128 + status.unsigned_abs()
+    } else {
+        status as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::{pane, panes_on_tab};
+
+    fn manifest(exited: bool, exit_status: Option<i32>) -> PaneManifest {
+        let pane = PaneInfo {
+            terminal_command: Some("cargo test".to_string()),
+            exited,
+            exit_status,
+            ..pane(3)
+        };
+        panes_on_tab(1, [pane])
+    }
+
+    #[test]
+    fn reports_each_run_once_when_it_exits() {
+        let mut watch = CommandWatch::default();
+        assert!(watch.update(&manifest(false, None), 0.0).is_empty());
+        let finished = watch.update(&manifest(true, Some(101)), 5.0);
+        assert_eq!(finished, [(1, 3, CommandResult {
+            command: Some("cargo test".to_string()),
+            duration_ms: None,
+            ..CommandResult::exited(101)
+        })]);
+        assert!(watch.update(&manifest(true, Some(101)), 6.0).is_empty());
+
+        // Re-run after load, the duration is known this time
+        watch.update(&manifest(false, None), 10.0);
+        let finished = watch.update(&manifest(true, Some(0)), 12.5);
+        assert_eq!(finished[0].2.duration_ms, Some(2500));
+    }
+
+    #[test]
+    fn killed_commands_report_128_plus_the_signal() {
+        let mut watch = CommandWatch::default();
+        watch.update(&manifest(false, None), 0.0);
+        let finished = watch.update(&manifest(true, Some(-9)), 1.0);
+        assert_eq!(finished[0].2.exit_code, 137);
+    }
+
+    #[test]
+    fn panes_exited_before_load_are_not_reported() {
+        let mut watch = CommandWatch::default();
+        assert!(watch.update(&manifest(true, Some(1)), 0.0).is_empty());
+    }
+}
@@ -27,7 +27,7 @@ fn install() -> ExitCode {
     
     // Build the project in release mode
     let build_status = Command::new("cargo")
-        .args(&["build", "--release"])
+        .args(["build", "--release"])
         .status();
 
     match build_status {